@@ -0,0 +1,68 @@
+use std::fs;
+use std::path::Path;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use aoc::{day08, day13, generate, registry};
+use aoc::Solution;
+
+/// Variants that do not finish on the real input in any reasonable time.
+const TOO_SLOW: &[(u8, u8, &str)] = &[(13, 2, "brute")];
+
+fn read_input(day: u8) -> Option<String> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(format!("day{}", day))
+        .join("input");
+    fs::read_to_string(path).ok()
+}
+
+/// Parse and every variant of both parts of every registered day, on the
+/// real input.
+fn bench_solvers(c: &mut Criterion) {
+    for solver in registry::all() {
+        let input = match read_input(solver.day()) {
+            Some(input) => input,
+            None => continue,
+        };
+        let puzzle = solver.parse(&input).expect("Invalid input file");
+
+        let mut group = c.benchmark_group(format!("day{:02}", solver.day()));
+        // Some parts take seconds, keep the total time bearable
+        group.sample_size(10);
+        group.bench_function("parse", |b| b.iter(|| solver.parse(&input)));
+        for part in 1..=2 {
+            for variant in solver.variants(part) {
+                if TOO_SLOW.contains(&(solver.day(), part, variant)) {
+                    continue;
+                }
+                group.bench_function(format!("part{}/{}", part, variant), |b| {
+                    b.iter(|| solver.solve_variant(part, variant, puzzle.as_ref()))
+                });
+            }
+        }
+        group.finish();
+    }
+}
+
+/// Variants that are too slow for the real input, compared on the example.
+fn bench_alternatives(c: &mut Criterion) {
+    let buses = day13::parse_buses_with_offset("7,13,x,x,59,x,31,19").expect("Invalid example");
+    let mut group = c.benchmark_group("day13 star two");
+    group.bench_function("brute force", |b| b.iter(|| day13::star_two(&buses)));
+    group.bench_function("peeked", |b| b.iter(|| day13::star_two_peeked_solution(&buses)));
+    group.finish();
+
+    // The ways to repair boot code only grow apart on programs longer than the real one
+    let generated = day08::generate(&mut generate::random(1), 2000);
+    let program = day08::Day08::parse(&generated.input).expect("Invalid generated input");
+    let mut group = c.benchmark_group("day08 star two, 2000 instructions");
+    group.sample_size(10);
+    group.bench_function("dfs", |b| b.iter(|| day08::star_two(&program)));
+    group.bench_function("brute", |b| b.iter(|| day08::star_two_original(&program)));
+    group.bench_function("cfg", |b| b.iter(|| day08::star_two_control_flow(&program)));
+    group.finish();
+}
+
+criterion_group!(benches, bench_solvers, bench_alternatives);
+criterion_main!(benches);
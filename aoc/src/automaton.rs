@@ -0,0 +1,418 @@
+//! Cellular automata in any number of dimensions, as in days 11, 17 and 24:
+//! every step all cells look at their neighbours at once and live or die
+//! according to a birth/survival rule.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::grid::Grid;
+
+/// Position of a cell.
+pub type Point<const N: usize> = [isize; N];
+
+/// Which cells count as the neighbours of a cell.
+#[derive(Debug, Clone)]
+pub enum Neighbourhood<const N: usize> {
+    /// Every cell at most one step away along each axis, diagonals included
+    Moore,
+    /// Fixed steps away, e.g. the six neighbours of a hexagonal tile
+    Offsets(Vec<Point<N>>),
+    /// The first cell seen in every Moore direction, looking past the
+    /// positions without a cell. Only makes sense for dense automata.
+    LineOfSight,
+}
+
+/// All steps to the cells around the origin, diagonals included.
+pub fn moore_offsets<const N: usize>() -> Vec<Point<N>> {
+    let mut offsets: Vec<Point<N>> = vec![[0; N]];
+    for axis in 0..N {
+        offsets = offsets
+            .into_iter()
+            .flat_map(|offset| {
+                (-1..=1).map(move |d| {
+                    let mut next = offset;
+                    next[axis] = d;
+                    next
+                })
+            })
+            .collect();
+    }
+    offsets.retain(|offset| offset.iter().any(|&d| d != 0));
+    offsets
+}
+
+fn add<const N: usize>(a: Point<N>, b: Point<N>) -> Point<N> {
+    let mut result = a;
+    for (r, d) in result.iter_mut().zip(b.iter()) {
+        *r += d;
+    }
+    result
+}
+
+/// When a cell is alive in the next step, by its number of live neighbours.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rule {
+    pub birth: Vec<usize>,
+    pub survival: Vec<usize>,
+}
+
+impl Rule {
+    pub fn new(birth: &[usize], survival: &[usize]) -> Rule {
+        Rule {
+            birth: birth.to_vec(),
+            survival: survival.to_vec(),
+        }
+    }
+
+    pub fn next(&self, alive: bool, neighbours: usize) -> bool {
+        if alive {
+            self.survival.contains(&neighbours)
+        } else {
+            self.birth.contains(&neighbours)
+        }
+    }
+}
+
+/// An endless space that only keeps track of the live cells.
+#[derive(Debug, Clone)]
+pub struct Sparse<const N: usize> {
+    alive: HashSet<Point<N>>,
+    offsets: Vec<Point<N>>,
+    rule: Rule,
+}
+
+impl<const N: usize> Sparse<N> {
+    pub fn new(alive: impl IntoIterator<Item = Point<N>>, neighbourhood: &Neighbourhood<N>, rule: Rule) -> Sparse<N> {
+        // Only the neighbours of live cells are looked at
+        assert!(!rule.birth.contains(&0), "Cells without live neighbours cannot be born in an endless space");
+        let offsets = match neighbourhood {
+            Neighbourhood::Moore => moore_offsets(),
+            Neighbourhood::Offsets(offsets) => offsets.clone(),
+            Neighbourhood::LineOfSight => panic!("An endless space has nothing to look past"),
+        };
+        Sparse {
+            alive: alive.into_iter().collect(),
+            offsets,
+            rule,
+        }
+    }
+
+    /// Move on to the next generation, returning how many cells changed.
+    pub fn step(&mut self) -> usize {
+        #[cfg(not(feature = "parallel"))]
+        let counts = self.alive.iter().fold(HashMap::new(), |counts, cell| self.count_around(counts, cell));
+        #[cfg(feature = "parallel")]
+        let counts = self.neighbour_counts();
+
+        let mut next = HashSet::new();
+        for (&cell, &count) in counts.iter() {
+            if self.rule.next(self.alive.contains(&cell), count) {
+                next.insert(cell);
+            }
+        }
+        // Live cells without any live neighbours were not counted above
+        for &cell in self.alive.iter() {
+            if !counts.contains_key(&cell) && self.rule.next(true, 0) {
+                next.insert(cell);
+            }
+        }
+
+        let changed = next.symmetric_difference(&self.alive).count();
+        self.alive = next;
+        changed
+    }
+
+    pub fn run(&mut self, steps: usize) {
+        for _ in 0..steps {
+            self.step();
+        }
+    }
+
+    /// Add one to the count of every neighbour of a live cell.
+    fn count_around(&self, mut counts: HashMap<Point<N>, usize>, &cell: &Point<N>) -> HashMap<Point<N>, usize> {
+        for &offset in self.offsets.iter() {
+            *counts.entry(add(cell, offset)).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// The number of live neighbours of every cell next to a live cell,
+    /// counted by every thread for its share of the live cells and added up.
+    #[cfg(feature = "parallel")]
+    fn neighbour_counts(&self) -> HashMap<Point<N>, usize> {
+        self.alive
+            .par_iter()
+            .fold(HashMap::new, |counts, cell| self.count_around(counts, cell))
+            .reduce(HashMap::new, |mut total, counts| {
+                for (cell, count) in counts {
+                    *total.entry(cell).or_insert(0) += count;
+                }
+                total
+            })
+    }
+
+    pub fn is_alive(&self, cell: &Point<N>) -> bool {
+        self.alive.contains(cell)
+    }
+
+    pub fn alive(&self) -> impl Iterator<Item = &Point<N>> + '_ {
+        self.alive.iter()
+    }
+
+    pub fn nr_alive(&self) -> usize {
+        self.alive.len()
+    }
+}
+
+/// Every 2D slice through the live cells that has any, the way day 17 shows
+/// them: a line with the other coordinates (`z`, `w`, ...), then the slice
+/// with `#` for live cells. All slices show the same x/y area.
+impl<const N: usize> fmt::Display for Sparse<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.alive.is_empty() || N < 2 {
+            return Ok(());
+        }
+        let min = |axis: usize| self.alive.iter().map(|cell| cell[axis]).min().unwrap_or(0);
+        let max = |axis: usize| self.alive.iter().map(|cell| cell[axis]).max().unwrap_or(0);
+        let mut slices: Vec<&[isize]> = self.alive.iter().map(|cell| &cell[2..]).collect();
+        slices.sort_unstable();
+        slices.dedup();
+
+        for (i, slice) in slices.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            if !slice.is_empty() {
+                let axes: Vec<String> = slice
+                    .iter()
+                    .enumerate()
+                    .map(|(axis, value)| match axis {
+                        0 => format!("z={}", value),
+                        1 => format!("w={}", value),
+                        _ => format!("d{}={}", axis + 3, value),
+                    })
+                    .collect();
+                writeln!(f, "{}", axes.join(", "))?;
+            }
+            for y in min(1)..=max(1) {
+                for x in min(0)..=max(0) {
+                    let mut cell = [0; N];
+                    cell[0] = x;
+                    cell[1] = y;
+                    cell[2..].copy_from_slice(slice);
+                    write!(f, "{}", if self.alive.contains(&cell) { '#' } else { '.' })?;
+                }
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A bounded box in which some positions hold a cell and others do not,
+/// like the seats and the floor of day 11.
+#[derive(Debug, Clone)]
+pub struct Dense<const N: usize> {
+    size: [usize; N],
+    // None for positions without a cell
+    cells: Vec<Option<bool>>,
+    // The neighbours never change, so they are only looked up once
+    neighbours: Vec<Vec<usize>>,
+    rule: Rule,
+}
+
+impl<const N: usize> Dense<N> {
+    pub fn new(
+        size: [usize; N],
+        cell: impl Fn(Point<N>) -> Option<bool>,
+        neighbourhood: &Neighbourhood<N>,
+        rule: Rule,
+    ) -> Dense<N> {
+        let volume = size.iter().product();
+        let mut dense = Dense {
+            size,
+            cells: Vec::with_capacity(volume),
+            neighbours: Vec::with_capacity(volume),
+            rule,
+        };
+        for i in 0..volume {
+            let value = cell(dense.point(i));
+            dense.cells.push(value);
+        }
+
+        let (offsets, look_past) = match neighbourhood {
+            Neighbourhood::Moore => (moore_offsets(), false),
+            Neighbourhood::Offsets(offsets) => (offsets.clone(), false),
+            Neighbourhood::LineOfSight => (moore_offsets(), true),
+        };
+        for i in 0..volume {
+            let mut neighbours = vec![];
+            if dense.cells[i].is_some() {
+                let point = dense.point(i);
+                for &offset in offsets.iter() {
+                    let mut pos = add(point, offset);
+                    while let Some(j) = dense.index(pos) {
+                        if dense.cells[j].is_some() {
+                            neighbours.push(j);
+                            break;
+                        }
+                        if !look_past {
+                            break;
+                        }
+                        pos = add(pos, offset);
+                    }
+                }
+            }
+            dense.neighbours.push(neighbours);
+        }
+
+        dense
+    }
+
+    fn index(&self, point: Point<N>) -> Option<usize> {
+        let mut index = 0;
+        for axis in (0..N).rev() {
+            if point[axis] < 0 || point[axis] as usize >= self.size[axis] {
+                return None;
+            }
+            index = index * self.size[axis] + point[axis] as usize;
+        }
+        Some(index)
+    }
+
+    fn point(&self, mut index: usize) -> Point<N> {
+        let mut point = [0; N];
+        for (coord, &size) in point.iter_mut().zip(self.size.iter()) {
+            *coord = (index % size) as isize;
+            index /= size;
+        }
+        point
+    }
+
+    /// The state of the cell at a position: `None` without a cell there.
+    pub fn get(&self, point: Point<N>) -> Option<bool> {
+        self.index(point).and_then(|i| self.cells[i])
+    }
+
+    /// Move on to the next generation, returning how many cells changed.
+    pub fn step(&mut self) -> usize {
+        let next_cell = |(cell, neighbours): (&Option<bool>, &Vec<usize>)| {
+            cell.map(|alive| {
+                let count = neighbours.iter().filter(|&&j| self.cells[j] == Some(true)).count();
+                self.rule.next(alive, count)
+            })
+        };
+        #[cfg(not(feature = "parallel"))]
+        let next: Vec<Option<bool>> = self.cells.iter().zip(self.neighbours.iter()).map(next_cell).collect();
+        #[cfg(feature = "parallel")]
+        let next: Vec<Option<bool>> = self.cells.par_iter().zip(self.neighbours.par_iter()).map(next_cell).collect();
+
+        let changed = next.iter().zip(self.cells.iter()).filter(|(a, b)| a != b).count();
+        self.cells = next;
+        changed
+    }
+
+    pub fn run(&mut self, steps: usize) {
+        for _ in 0..steps {
+            self.step();
+        }
+    }
+
+    /// Keep stepping until nothing changes any more, giving the number of
+    /// steps that changed something.
+    pub fn run_until_stable(&mut self) -> usize {
+        let mut steps = 0;
+        while self.step() > 0 {
+            steps += 1;
+        }
+        steps
+    }
+
+    pub fn nr_alive(&self) -> usize {
+        self.cells.iter().filter(|&&cell| cell == Some(true)).count()
+    }
+}
+
+impl Dense<2> {
+    /// A 2D automaton with a cell for every grid position that `cell` maps to
+    /// a state.
+    pub fn from_grid<T>(
+        grid: &Grid<T>,
+        cell: impl Fn(&T) -> Option<bool>,
+        neighbourhood: &Neighbourhood<2>,
+        rule: Rule,
+    ) -> Dense<2> {
+        Dense::new(
+            [grid.width(), grid.height()],
+            |[x, y]| grid.get(x, y).and_then(&cell),
+            neighbourhood,
+            rule,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "parallel")]
+    use std::collections::HashMap;
+
+    use super::{Dense, Neighbourhood, Rule, Sparse};
+
+    // The glider from Conway's Game of Life, B3/S23
+    static GLIDER: [[isize; 2]; 5] = [[1, 0], [2, 1], [0, 2], [1, 2], [2, 2]];
+
+    #[test]
+    fn test_moore_offsets() {
+        assert_eq!(super::moore_offsets::<1>(), vec![[-1], [1]]);
+        assert_eq!(super::moore_offsets::<2>().len(), 8);
+        assert_eq!(super::moore_offsets::<4>().len(), 80);
+    }
+
+    #[test]
+    fn test_sparse() {
+        let mut life = Sparse::new(GLIDER.iter().copied(), &Neighbourhood::Moore, Rule::new(&[3], &[2, 3]));
+        life.run(4);
+        // A glider moves one cell diagonally every 4 generations
+        assert_eq!(life.nr_alive(), 5);
+        for &[x, y] in GLIDER.iter() {
+            assert!(life.is_alive(&[x + 1, y + 1]));
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_counts() {
+        let glider = GLIDER.iter().map(|&[x, y]| [x, y, 0]);
+        let mut cube = Sparse::new(glider, &Neighbourhood::Moore, Rule::new(&[3], &[2, 3]));
+        for _ in 0..4 {
+            let sequential = cube.alive.iter().fold(HashMap::new(), |counts, cell| cube.count_around(counts, cell));
+            assert_eq!(cube.neighbour_counts(), sequential);
+            cube.step();
+        }
+    }
+
+    #[test]
+    fn test_dense() {
+        let cell = |[x, y]: [isize; 2]| Some(GLIDER.contains(&[x, y]));
+        let rule = Rule::new(&[3], &[2, 3]);
+        let mut life = Dense::new([8, 8], cell, &Neighbourhood::Moore, rule);
+        assert_eq!(life.step(), 4);
+        life.run(3);
+        assert_eq!(life.nr_alive(), 5);
+        assert_eq!(life.get([3, 3]), Some(true));
+        assert_eq!(life.get([0, 2]), Some(false));
+        assert_eq!(life.get([8, 0]), None);
+
+        // Looking past the holes in the middle row
+        let cell = |[x, y]: [isize; 2]| if y == 1 && x != 0 { None } else { Some(x == 2 && y == 2) };
+        let life = Dense::new([3, 3], cell, &Neighbourhood::LineOfSight, Rule::new(&[1], &[]));
+        assert_eq!(life.neighbours[0].len(), 3);
+        let mut life = life;
+        life.step();
+        assert_eq!(life.get([2, 0]), Some(true));
+        assert_eq!(life.get([0, 0]), Some(true));
+        assert_eq!(life.get([1, 0]), Some(false));
+    }
+}
@@ -0,0 +1,173 @@
+use std::collections::HashSet;
+use std::vec::Vec;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::error::ParseError;
+use crate::generate::{Generated, Random};
+use crate::solution::Solution;
+
+#[derive(Debug, PartialEq)]
+pub struct CorrectNumbersNotFoundError;
+
+pub fn parse_numbers(lines: impl Iterator<Item = String>) -> Result<Vec<i32>, ParseError> {
+    let mut numbers: Vec<i32> = Vec::new();
+    for (i, number) in lines.enumerate() {
+        let parsed: i32 = number.parse().map_err(|e| {
+            ParseError::new(Day01::DAY, &number, format!("invalid number ({})", e)).at_line(i + 1)
+        })?;
+        numbers.push(parsed);
+    }
+    Ok(numbers)
+}
+
+pub fn star_one(numbers: &[i32]) -> Result<(i32, i32), CorrectNumbersNotFoundError> {
+    for (i, first) in numbers.iter().enumerate() {
+        for (_, second) in numbers.iter().enumerate().skip(i) {
+            if first + second == 2020 {
+                return Ok((*first, *second));
+            }
+        }
+    }
+    Err(CorrectNumbersNotFoundError)
+}
+
+pub fn star_two(numbers: &[i32]) -> Result<(i32, i32, i32), CorrectNumbersNotFoundError> {
+    for (i, first) in numbers.iter().enumerate() {
+        for (j, second) in numbers.iter().enumerate().skip(i) {
+            for (_, third) in numbers.iter().enumerate().skip(j) {
+                if first + second + third == 2020 {
+                    return Ok((*first, *second, *third));
+                }
+            }
+        }
+    }
+    Err(CorrectNumbersNotFoundError)
+}
+
+// Number of pairs and triples summing to 2020, where an entry may be used
+// more than once like `star_one` and `star_two` do
+fn nr_sums(numbers: &[i32]) -> (usize, usize) {
+    let mut pairs = 0;
+    let mut triples = 0;
+    for i in 0..numbers.len() {
+        for j in i..numbers.len() {
+            if numbers[i] + numbers[j] == 2020 {
+                pairs += 1;
+            }
+            for k in j..numbers.len() {
+                if numbers[i] + numbers[j] + numbers[k] == 2020 {
+                    triples += 1;
+                }
+            }
+        }
+    }
+    (pairs, triples)
+}
+
+/// An expense report of `size` entries (at least 5, at most about 1000) in
+/// which exactly one pair and exactly one triple sum to 2020.
+pub fn generate(rng: &mut Random, size: usize) -> Generated {
+    let planted = loop {
+        let first = rng.gen_range(1011..2000);
+        let second = rng.gen_range(50..600);
+        let third = rng.gen_range(50..600);
+        let planted = [first, 2020 - first, second, third, 2020 - second - third];
+        if nr_sums(&planted) == (1, 1) {
+            break planted;
+        }
+    };
+
+    // The other entries are all above 1010 so they never add up to 2020
+    // among themselves; leave out those that would with the planted ones
+    let mut forbidden: HashSet<i32> = planted.iter().copied().collect();
+    for a in planted.iter() {
+        forbidden.insert(2020 - a);
+        for b in planted.iter() {
+            forbidden.insert(2020 - a - b);
+        }
+    }
+    let mut others: Vec<i32> = (1011..2020).filter(|x| !forbidden.contains(x)).collect();
+    others.shuffle(rng);
+
+    let mut numbers = planted.to_vec();
+    numbers.extend(others.into_iter().take(size.saturating_sub(planted.len())));
+    numbers.shuffle(rng);
+    let lines: Vec<String> = numbers.iter().map(|x| x.to_string()).collect();
+
+    Generated {
+        input: lines.join("\n"),
+        answer1: Some((planted[0] * planted[1]).to_string()),
+        answer2: Some((planted[2] * planted[3] * planted[4]).to_string()),
+    }
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    type Puzzle = Vec<i32>;
+
+    fn parse_lines(lines: impl Iterator<Item = String>) -> Result<Self::Puzzle, ParseError> {
+        parse_numbers(lines)
+    }
+
+    fn part1(numbers: &Self::Puzzle) -> Option<String> {
+        let (num1, num2) = star_one(numbers).ok()?;
+        Some((num1 * num2).to_string())
+    }
+
+    fn part2(numbers: &Self::Puzzle) -> Option<String> {
+        let (num1, num2, num3) = star_two(numbers).ok()?;
+        Some((num1 * num2 * num3).to_string())
+    }
+
+    fn generate(rng: &mut Random, size: usize) -> Option<Generated> {
+        Some(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_star_one() {
+        let numbers = vec![1721, 979, 366, 299, 675, 1456];
+        let (num1, num2) = super::star_one(&numbers).expect("Invalid test data");
+        assert_eq!(num1 + num2, 2020);
+        assert_eq!(num1 * num2, 514579);
+
+        let numbers = vec![1, 2];
+        let result = super::star_one(&numbers);
+        assert_eq!(result, Err(super::CorrectNumbersNotFoundError));
+    }
+
+    #[test]
+    fn test_star_two() {
+        let numbers = vec![1721, 979, 366, 299, 675, 1456];
+        let (num1, num2, num3) = super::star_two(&numbers).expect("Invalid test data");
+        assert_eq!(num1 + num2 + num3, 2020);
+        assert_eq!(num1 * num2 * num3, 241861950);
+
+        let numbers = vec![1, 2, 3];
+        let result = super::star_two(&numbers);
+        assert_eq!(result, Err(super::CorrectNumbersNotFoundError));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        use crate::solution::Solution;
+
+        let err = super::Day01::parse("1721\n979\n36x6\n").expect_err("Invalid number accepted");
+        assert_eq!(err.line, Some(3));
+        assert_eq!(err.text, "36x6");
+    }
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..5 {
+            crate::generate::check::<super::Day01>(seed, 200);
+        }
+        let generated = crate::generate::check::<super::Day01>(0, 1);
+        assert_eq!(generated.input.lines().count(), 5);
+    }
+}
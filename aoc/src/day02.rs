@@ -0,0 +1,225 @@
+use std::vec::Vec;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::error::ParseError;
+use crate::generate::{Generated, Random};
+use crate::solution::Solution;
+
+fn crop_letters(s: &str, pos: usize) -> &str {
+    match s.char_indices().nth(pos) {
+        Some((pos, _)) => &s[pos..],
+        None => "",
+    }
+}
+
+fn parse_entry(rawrule: &str) -> Result<Entry, ParseError> {
+    let invalid = |reason: &str| ParseError::new(Day02::DAY, rawrule, reason);
+
+    let (rule, pass) = rawrule.split_once(':').ok_or_else(|| invalid("missing ':'"))?;
+    let pass = crop_letters(pass, 1);
+    let (bounds, incl) = rule.split_once(' ').ok_or_else(|| invalid("missing letter"))?;
+    let incl = incl.chars().next().ok_or_else(|| invalid("missing letter"))?;
+    let (lower, upper) = bounds.split_once('-').ok_or_else(|| invalid("missing '-' in range"))?;
+    let lower: usize = lower.parse().map_err(|_| invalid("invalid lower bound"))?;
+    let upper: usize = upper.parse().map_err(|_| invalid("invalid upper bound"))?;
+    if lower == 0 || lower > upper {
+        return Err(invalid("range must start at 1 and not be reversed"));
+    }
+
+    Ok(Entry {
+        lower,
+        upper,
+        incl,
+        pass: pass.to_string(),
+    })
+}
+
+pub fn parse_lines(lines: impl Iterator<Item = String>) -> Result<Vec<Entry>, ParseError> {
+    let mut entries: Vec<Entry> = Vec::new();
+    for (i, rawrule) in lines.enumerate() {
+        entries.push(parse_entry(&rawrule).map_err(|e| e.at_line(i + 1))?);
+    }
+    Ok(entries)
+}
+
+#[derive(Debug)]
+pub struct Entry {
+    pub lower: usize,
+    pub upper: usize,
+    pub incl: char,
+    pub pass: String,
+}
+
+pub fn star_one(entries: &[Entry]) -> (usize, usize) {
+    let mut valid: usize = 0;
+    let mut invalid: usize = 0;
+    for entry in entries {
+        let count = entry.pass.matches(entry.incl).count();
+        if count >= entry.lower && count <= entry.upper {
+            valid += 1;
+        } else {
+            invalid += 1;
+        }
+    }
+    (valid, invalid)
+}
+
+pub fn star_two(entries: &[Entry]) -> (usize, usize) {
+    let mut valid: usize = 0;
+    let mut invalid: usize = 0;
+    for entry in entries {
+        let match1 = entry.pass.chars().nth(entry.lower - 1) == Some(entry.incl);
+        let match2 = entry.pass.chars().nth(entry.upper - 1) == Some(entry.incl);
+        if (match1 && !match2) || (!match1 && match2) {
+            valid += 1;
+        } else {
+            invalid += 1;
+        }
+    }
+    (valid, invalid)
+}
+
+/// A list of `size` passwords with their policies, each made to be valid or
+/// not by chance for either reading of the policy.
+pub fn generate(rng: &mut Random, size: usize) -> Generated {
+    let mut lines = vec![];
+    let mut valid_one = 0;
+    let mut valid_two = 0;
+    for _ in 0..size {
+        let incl = rng.gen_range(b'a'..=b'z') as char;
+        let lower = rng.gen_range(1..=8);
+        let upper = rng.gen_range(lower + 1..=lower + 10);
+        let len = rng.gen_range(upper..=upper + 6);
+
+        // The letter of the policy a number of times, other letters elsewhere
+        let count = rng.gen_range(0..=upper + 2).min(len);
+        let mut pass: Vec<char> = (0..len)
+            .map(|i| {
+                if i < count {
+                    return incl;
+                }
+                loop {
+                    let other = rng.gen_range(b'a'..=b'z') as char;
+                    if other != incl {
+                        return other;
+                    }
+                }
+            })
+            .collect();
+        pass.shuffle(rng);
+
+        if count >= lower && count <= upper {
+            valid_one += 1;
+        }
+        if (pass[lower - 1] == incl) != (pass[upper - 1] == incl) {
+            valid_two += 1;
+        }
+        lines.push(format!("{}-{} {}: {}", lower, upper, incl, pass.into_iter().collect::<String>()));
+    }
+
+    Generated {
+        input: lines.join("\n"),
+        answer1: Some(valid_one.to_string()),
+        answer2: Some(valid_two.to_string()),
+    }
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    type Puzzle = Vec<Entry>;
+
+    fn parse_lines(lines: impl Iterator<Item = String>) -> Result<Self::Puzzle, ParseError> {
+        parse_lines(lines)
+    }
+
+    fn part1(entries: &Self::Puzzle) -> Option<String> {
+        let (valid, _) = star_one(entries);
+        Some(valid.to_string())
+    }
+
+    fn part2(entries: &Self::Puzzle) -> Option<String> {
+        let (valid, _) = star_two(entries);
+        Some(valid.to_string())
+    }
+
+    fn generate(rng: &mut Random, size: usize) -> Option<Generated> {
+        Some(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Entry;
+
+    #[test]
+    fn test_star_one() {
+        let entries = vec![
+            Entry {
+                lower: 1,
+                upper: 3,
+                incl: 'a',
+                pass: String::from("abcde"),
+            },
+            Entry {
+                lower: 1,
+                upper: 3,
+                incl: 'b',
+                pass: String::from("cdefg"),
+            },
+            Entry {
+                lower: 2,
+                upper: 9,
+                incl: 'c',
+                pass: String::from("ccccccccc"),
+            },
+        ];
+
+        let (valid, invalid) = super::star_one(&entries);
+        assert_eq!(valid, 2);
+        assert_eq!(invalid, 1);
+    }
+
+    #[test]
+    fn test_star_two() {
+        let entries = vec![
+            Entry {
+                lower: 1,
+                upper: 3,
+                incl: 'a',
+                pass: String::from("abcde"),
+            },
+            Entry {
+                lower: 1,
+                upper: 3,
+                incl: 'b',
+                pass: String::from("cdefg"),
+            },
+            Entry {
+                lower: 2,
+                upper: 9,
+                incl: 'c',
+                pass: String::from("ccccccccc"),
+            },
+        ];
+
+        let (valid, invalid) = super::star_two(&entries);
+        assert_eq!(valid, 1);
+        assert_eq!(invalid, 2);
+    }
+
+    #[test]
+    fn test_star_two_short_password() {
+        let entries = vec![super::parse_entry("1-5 a: ab").expect("Invalid test data")];
+        assert_eq!(super::star_two(&entries), (1, 0));
+    }
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..5 {
+            crate::generate::check::<super::Day02>(seed, 100);
+        }
+    }
+}
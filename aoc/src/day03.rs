@@ -0,0 +1,141 @@
+use core::fmt::Debug;
+use rand::Rng;
+
+use crate::error::ParseError;
+use crate::generate::{Generated, Random};
+use crate::grid::Grid;
+use crate::solution::Solution;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MapTile {
+    Empty,
+    Tree,
+}
+
+/// The map repeats endlessly to the right.
+pub type Map = Grid<MapTile>;
+
+pub fn parse_map<L: AsRef<str>>(lines: impl Iterator<Item = L>) -> Result<Map, ParseError> {
+    let map = Grid::from_lines(Day03::DAY, lines, |c| match c {
+        '.' => Ok(MapTile::Empty),
+        '#' => Ok(MapTile::Tree),
+        _ => Err("invalid map tile"),
+    })?;
+    Ok(map.wrapping_x())
+}
+
+/// Go down the map from the top left corner, `incr_x` right for every
+/// `incr_y` down, counting the trees on the way.
+pub fn nr_trees_for_slope(map: &Map, incr_x: usize, incr_y: usize) -> u32 {
+    map.walk((0, 0), (incr_x as isize, incr_y as isize))
+        .fold(0u32, |s, x| match x {
+            MapTile::Tree => s + 1,
+            MapTile::Empty => s,
+        })
+}
+
+pub fn star_one(map: &Map) -> u32 {
+    nr_trees_for_slope(map, 3, 1)
+}
+
+pub fn star_two(map: &Map) -> u32 {
+    let slopes = vec![(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+    slopes.into_iter().fold(1, |s, (x, y)| s * nr_trees_for_slope(map, x, y))
+}
+
+/// A map of `size` rows, 31 squares wide like the real ones, with about one
+/// square in four holding a tree.
+pub fn generate(rng: &mut Random, size: usize) -> Generated {
+    let width = 31;
+    let rows: Vec<Vec<bool>> = (0..size.max(1))
+        .map(|y| (0..width).map(|x| (x, y) != (0, 0) && rng.gen_bool(0.25)).collect())
+        .collect();
+
+    let trees = |right: usize, down: usize| -> u64 {
+        rows.iter()
+            .step_by(down)
+            .enumerate()
+            .filter(|(i, row)| row[(i * right) % width])
+            .count() as u64
+    };
+    let slopes = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+    let lines: Vec<String> = rows
+        .iter()
+        .map(|row| row.iter().map(|&tree| if tree { '#' } else { '.' }).collect())
+        .collect();
+
+    Generated {
+        input: lines.join("\n"),
+        answer1: Some(trees(3, 1).to_string()),
+        answer2: Some(slopes.iter().map(|&(right, down)| trees(right, down)).product::<u64>().to_string()),
+    }
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    type Puzzle = Map;
+
+    fn parse_lines(lines: impl Iterator<Item = String>) -> Result<Self::Puzzle, ParseError> {
+        parse_map(lines)
+    }
+
+    fn part1(map: &Self::Puzzle) -> Option<String> {
+        Some(star_one(map).to_string())
+    }
+
+    fn part2(map: &Self::Puzzle) -> Option<String> {
+        Some(star_two(map).to_string())
+    }
+
+    fn generate(rng: &mut Random, size: usize) -> Option<Generated> {
+        Some(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    static TEST_MAP: &str = "..##.......
+#...#...#..
+.#....#..#.
+..#.#...#.#
+.#...##..#.
+..#.##.....
+.#.#.#....#
+.#........#
+#.##...#...
+#...##....#
+.#..#...#.#";
+
+    #[test]
+    fn test_star_one() {
+        let map = super::parse_map(TEST_MAP.lines()).expect("Invalid test data");
+
+        assert_eq!(map.width(), 11);
+        assert_eq!(map.height(), 11);
+
+        assert_eq!(map.get(0, 0), Some(&super::MapTile::Empty));
+        assert_eq!(map.get(0, 1), Some(&super::MapTile::Tree));
+        assert_eq!(map.get(11, 1), Some(&super::MapTile::Tree));
+        assert_eq!(map.get(0, 11), None);
+
+        let nr_trees = super::star_one(&map);
+        assert_eq!(nr_trees, 7);
+    }
+
+    #[test]
+    fn test_star_two() {
+        let map = super::parse_map(TEST_MAP.lines()).expect("Invalid test data");
+
+        let nr_trees = super::star_two(&map);
+        assert_eq!(nr_trees, 336);
+    }
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..5 {
+            crate::generate::check::<super::Day03>(seed, 50);
+        }
+    }
+}
@@ -0,0 +1,445 @@
+use core::fmt::Debug;
+use std::vec::Vec;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::error::ParseError;
+use crate::generate::{Generated, Random};
+use crate::solution::Solution;
+
+#[derive(Debug)]
+pub struct Passport {
+    pub byr: Option<String>, // (Birth Year)
+    pub iyr: Option<String>, // (Issue Year)
+    pub eyr: Option<String>, // (Expiration Year)
+    pub hgt: Option<String>, // (Height)
+    pub hcl: Option<String>, // (Hair Color)
+    pub ecl: Option<String>, // (Eye Color)
+    pub pid: Option<String>, // (Passport ID)
+    pub cid: Option<String>, // (Country ID)
+}
+
+fn is_parseable_year_between(elem: &str, lbound: usize, ubound: usize) -> bool {
+    if elem.chars().count() != 4 || !elem.chars().all(char::is_numeric) {
+        return false;
+    }
+    let elem_parsed = elem.parse::<usize>();
+    if elem_parsed.is_err() {
+        return false;
+    }
+    let elem_parsed = elem_parsed.unwrap();
+    if elem_parsed < lbound || elem_parsed > ubound {
+        return false;
+    }
+
+    true
+}
+
+impl Passport {
+    pub fn is_somewhat_valid(&self) -> bool {
+        self.byr.is_some()
+            && self.iyr.is_some()
+            && self.eyr.is_some()
+            && self.hgt.is_some()
+            && self.hcl.is_some()
+            && self.ecl.is_some()
+            && self.pid.is_some()
+    }
+
+    pub fn is_somewhat_more_valid(&self) -> bool {
+        if !self.is_somewhat_valid() {
+            return false;
+        }
+
+        // byr
+        if !is_parseable_year_between(self.byr.as_ref().unwrap(), 1920, 2002) {
+            return false;
+        }
+
+        // iyr
+        if !is_parseable_year_between(self.iyr.as_ref().unwrap(), 2010, 2020) {
+            return false;
+        }
+
+        // eyr
+        if !is_parseable_year_between(self.eyr.as_ref().unwrap(), 2020, 2030) {
+            return false;
+        }
+
+        // hgt
+        let hgt = self.hgt.as_ref().unwrap();
+        let hgt_nr: &str;
+        let hgt_lbound: usize;
+        let hgt_ubound: usize;
+        if hgt.ends_with("cm") {
+            hgt_nr = &hgt[..hgt.len()-2];
+            hgt_lbound = 150;
+            hgt_ubound = 193;
+        } else if hgt.ends_with("in") {
+            hgt_nr = &hgt[..hgt.len()-2];
+            hgt_lbound = 59;
+            hgt_ubound = 76;
+        } else {
+            return false;
+        }
+        let hgt_parsed = hgt_nr.parse::<usize>();
+        if hgt_parsed.is_err() {
+            return false;
+        }
+        let hgt_parsed = hgt_parsed.unwrap();
+        if hgt_parsed < hgt_lbound || hgt_parsed > hgt_ubound {
+            return false;
+        }
+
+        // hcl
+        let hcl = self.hcl.as_ref().unwrap();
+        if hcl.chars().count() != 7 {
+            return false;
+        }
+        if !hcl.starts_with('#') {
+            return false;
+        }
+        if !(hcl.chars().skip(1).all(|x| char::is_ascii_hexdigit(&x))) {
+            return false;
+        }
+
+        // ecl
+        let ecl = self.ecl.as_ref().unwrap();
+        match &ecl[..] {
+            "amb" => (),
+            "blu" => (),
+            "brn" => (),
+            "gry" => (),
+            "grn" => (),
+            "hzl" => (),
+            "oth" => (),
+            _ => return false,
+        }
+
+        // pid
+        let pid = self.pid.as_ref().unwrap();
+        if pid.chars().count() != 9 {
+            return false;
+        }
+        if !(pid.chars().all(|x| char::is_ascii_digit(&x))) {
+            return false;
+        }
+
+        true
+    }
+}
+
+pub fn parse_passportlist(mut passports: Vec<Passport>, line: String) -> Result<Vec<Passport>, ParseError> {
+    if passports.is_empty() || line.trim().is_empty() {
+        passports.push(Passport { byr: None, iyr: None, eyr: None, hgt: None, hcl: None, ecl: None, pid: None, cid: None })
+    }
+    let pass = passports.last_mut().unwrap();
+    for elem in line.split_whitespace() {
+        let invalid = |reason| ParseError::new(Day04::DAY, elem, reason);
+        let mut kvp = elem.split(':');
+        let name = kvp.next().ok_or_else(|| invalid("missing field name"))?;
+        let value = kvp.next().ok_or_else(|| invalid("missing ':' after field name"))?;
+        if kvp.next().is_some() {
+            return Err(invalid("more than one ':' in field"));
+        }
+
+        match name {
+            "byr" => pass.byr = Some(String::from(value)),
+            "iyr" => pass.iyr = Some(String::from(value)),
+            "eyr" => pass.eyr = Some(String::from(value)),
+            "hgt" => pass.hgt = Some(String::from(value)),
+            "hcl" => pass.hcl = Some(String::from(value)),
+            "ecl" => pass.ecl = Some(String::from(value)),
+            "pid" => pass.pid = Some(String::from(value)),
+            "cid" => pass.cid = Some(String::from(value)),
+            _ => return Err(invalid("unknown field")),
+        }
+    }
+
+    Ok(passports)        
+}
+
+pub fn star_one(passports: &[Passport]) -> usize {
+    passports.iter().fold(
+        0usize,
+        |s, x| if x.is_somewhat_valid() { s + 1 } else { s }
+    )
+}
+
+pub fn star_two(passports: &[Passport]) -> usize {
+    passports.iter().fold(
+        0usize,
+        |s, x| if x.is_somewhat_more_valid() { s + 1 } else { s }
+    )
+}
+
+const REQUIRED: [&str; 7] = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
+const EYE_COLORS: [&str; 7] = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
+
+// A year in the given range, or one that is not
+fn year(rng: &mut Random, lbound: usize, ubound: usize, valid: bool) -> String {
+    let year = match (valid, rng.gen_bool(0.5)) {
+        (true, _) => rng.gen_range(lbound..=ubound),
+        (false, true) => rng.gen_range(lbound - 20..lbound),
+        (false, false) => rng.gen_range(ubound + 1..=ubound + 20),
+    };
+    year.to_string()
+}
+
+fn hex_digits(rng: &mut Random, len: usize) -> String {
+    (0..len).map(|_| *b"0123456789abcdef".choose(rng).unwrap() as char).collect()
+}
+
+// A value for a field that is either clearly valid or clearly invalid
+fn field_value(rng: &mut Random, field: &str, valid: bool) -> String {
+    match field {
+        "byr" => year(rng, 1920, 2002, valid),
+        "iyr" => year(rng, 2010, 2020, valid),
+        "eyr" => year(rng, 2020, 2030, valid),
+        "hgt" => match (valid, rng.gen_range(0..3)) {
+            (true, 0) => format!("{}in", rng.gen_range(59..=76)),
+            (true, _) => format!("{}cm", rng.gen_range(150..=193)),
+            (false, 0) => format!("{}in", rng.gen_range(77..=99)),
+            (false, 1) => format!("{}cm", rng.gen_range(100..150)),
+            (false, _) => rng.gen_range(59..=193).to_string(),
+        },
+        "hcl" => match (valid, rng.gen_bool(0.5)) {
+            (true, _) => format!("#{}", hex_digits(rng, 6)),
+            (false, true) => hex_digits(rng, 6),
+            (false, false) => format!("#{}", hex_digits(rng, 5)),
+        },
+        "ecl" => {
+            if valid {
+                EYE_COLORS.choose(rng).unwrap().to_string()
+            } else {
+                loop {
+                    let color = crate::generate::word(rng, 3, 3);
+                    if !EYE_COLORS.contains(&color.as_str()) {
+                        return color;
+                    }
+                }
+            }
+        }
+        "pid" => {
+            let len = if valid { 9 } else { *[8, 10].choose(rng).unwrap() };
+            (0..len).map(|_| rng.gen_range(b'0'..=b'9') as char).collect()
+        }
+        _ => rng.gen_range(100..350).to_string(),
+    }
+}
+
+/// A batch of `size` passports: about a third with missing fields, a third
+/// with all fields but an invalid value, and a third that is fully valid.
+pub fn generate(rng: &mut Random, size: usize) -> Generated {
+    let mut passports = vec![];
+    let mut complete = 0;
+    let mut valid = 0;
+    for _ in 0..size {
+        let kind = rng.gen_range(0..3);
+        let mut fields: Vec<&str> = REQUIRED.to_vec();
+        fields.shuffle(rng);
+        if kind == 0 {
+            // Leave out a few of the required fields
+            fields.truncate(REQUIRED.len() - rng.gen_range(1..=3));
+        } else {
+            complete += 1;
+        }
+        if rng.gen_bool(0.5) {
+            let pos = rng.gen_range(0..=fields.len());
+            fields.insert(pos, "cid");
+        }
+        let invalid = if kind == 1 { Some(*REQUIRED.choose(rng).unwrap()) } else { None };
+        if kind == 2 {
+            valid += 1;
+        }
+
+        // A few fields on every line
+        let mut lines = vec![];
+        let mut line = vec![];
+        for field in fields {
+            let value = field_value(rng, field, Some(field) != invalid);
+            line.push(format!("{}:{}", field, value));
+            if rng.gen_bool(0.3) {
+                lines.push(line.join(" "));
+                line.clear();
+            }
+        }
+        if !line.is_empty() {
+            lines.push(line.join(" "));
+        }
+        passports.push(lines.join("\n"));
+    }
+
+    Generated {
+        input: passports.join("\n\n"),
+        answer1: Some(complete.to_string()),
+        answer2: Some(valid.to_string()),
+    }
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    type Puzzle = Vec<Passport>;
+
+    fn parse_lines(lines: impl Iterator<Item = String>) -> Result<Self::Puzzle, ParseError> {
+        let mut last_line_empty = false;
+        let mut passports = lines.enumerate().try_fold(vec![], |s, (i, x)| {
+            last_line_empty = x.trim().is_empty();
+            parse_passportlist(s, x).map_err(|e| e.at_line(i + 1))
+        })?;
+        if last_line_empty {
+            passports.pop();
+        }
+
+        Ok(passports)
+    }
+
+    fn part1(passports: &Self::Puzzle) -> Option<String> {
+        Some(star_one(passports).to_string())
+    }
+
+    fn part2(passports: &Self::Puzzle) -> Option<String> {
+        Some(star_two(passports).to_string())
+    }
+
+    fn generate(rng: &mut Random, size: usize) -> Option<Generated> {
+        Some(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    static TEST_INPUT: &str = "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
+byr:1937 iyr:2017 cid:147 hgt:183cm
+
+iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
+hcl:#cfa07d byr:1929
+
+hcl:#ae17e1 iyr:2013
+eyr:2024
+ecl:brn pid:760753108 byr:1931
+hgt:179cm
+
+hcl:#cfa07d eyr:2025 pid:166559648
+iyr:2011 ecl:brn hgt:59in
+
+";
+
+    static TEST_INVALID: &str = "eyr:1972 cid:100
+hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926
+
+iyr:2019
+hcl:#602927 eyr:1967 hgt:170cm
+ecl:grn pid:012533040 byr:1946
+
+hcl:dab227 iyr:2012
+ecl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277
+
+hgt:59cm ecl:zzz
+eyr:2038 hcl:74454a iyr:2023
+pid:3556412378 byr:2007";
+
+    static TEST_VALID: &str = "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
+hcl:#623a2f
+
+eyr:2029 ecl:blu cid:129 byr:1989
+iyr:2014 pid:896056539 hcl:#a97842 hgt:165cm
+
+hcl:#888785
+hgt:164cm byr:2001 iyr:2015 cid:88
+pid:545766238 ecl:hzl
+eyr:2022
+
+iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719";
+
+    #[test]
+    fn test_star_one() {
+        let mut last_line_empty = false;
+        let mut passports = TEST_INPUT
+            .lines()
+            .map(|x| x.to_string())
+            .try_fold(
+                vec![],
+                |s, x| {
+                    last_line_empty = x.trim().is_empty();
+                    super::parse_passportlist(s, x)
+                },
+            )
+            .expect("Invalid test data");
+        if last_line_empty {
+            passports.pop();
+        }
+
+        assert_eq!(passports.len(), 4);
+        assert_eq!(passports[0].byr, Some(String::from("1937")));
+
+        let nr_valid = super::star_one(&passports);
+        assert_eq!(nr_valid, 2);
+    }
+
+    #[test]
+    fn test_star_two() {
+        let mut last_line_empty = false;
+        let mut passports = TEST_INVALID
+            .lines()
+            .map(|x| x.to_string())
+            .try_fold(
+                vec![],
+                |s, x| {
+                    last_line_empty = x.trim().is_empty();
+                    super::parse_passportlist(s, x)
+                },
+            )
+            .expect("Invalid test data");
+        if last_line_empty {
+            passports.pop();
+        }
+
+        assert_eq!(passports.len(), 4);
+        assert_eq!(passports[0].byr, Some(String::from("1926")));
+
+        let nr_valid = super::star_two(&passports);
+        assert_eq!(nr_valid, 0);
+
+        let mut passports = TEST_VALID
+        .lines()
+        .map(|x| x.to_string())
+        .try_fold(
+            vec![],
+            |s, x| {
+                last_line_empty = x.trim().is_empty();
+                super::parse_passportlist(s, x)
+            },
+        )
+        .expect("Invalid test data");
+        if last_line_empty {
+            passports.pop();
+        }
+
+        assert_eq!(passports.len(), 4);
+        assert_eq!(passports[0].byr, Some(String::from("1980")));
+
+        let nr_valid = super::star_two(&passports);
+        assert_eq!(nr_valid, 4);
+    }
+
+    #[test]
+    fn test_hair_color() {
+        let passport = |hcl: &str| {
+            let input = format!("byr:1980 iyr:2012 eyr:2030 hgt:74in ecl:grn pid:087499704 hcl:{}", hcl);
+            super::parse_passportlist(vec![], input).expect("Invalid test data")
+        };
+        assert_eq!(super::star_two(&passport("#623a2f")), 1);
+        assert_eq!(super::star_two(&passport("#623a2g")), 0);
+        assert_eq!(super::star_two(&passport("#123\u{20ac}45")), 0);
+    }
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..5 {
+            crate::generate::check::<super::Day04>(seed, 100);
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use core::fmt::Debug;
+use std::vec::Vec;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::error::ParseError;
+use crate::generate::{Generated, Random};
+use crate::solution::Solution;
+
+#[derive(Debug, Clone)]
+pub struct Seat {
+    pub row: usize,
+    pub column: usize,
+}
+
+impl Seat {
+    pub fn from_boardingpass(boardingpass: &str) -> Result<Self, ParseError> {
+        if boardingpass.chars().count() != 10 {
+            return Err(ParseError::new(Day05::DAY, boardingpass, "boarding pass is not 10 characters long"));
+        }
+        for (i, c) in boardingpass.chars().enumerate() {
+            let valid = if i < 7 { c == 'F' || c == 'B' } else { c == 'L' || c == 'R' };
+            if !valid {
+                return Err(ParseError::new(Day05::DAY, boardingpass, format!("invalid character '{}'", c)).at_column(i + 1));
+            }
+        }
+
+        let row_binary = boardingpass[..7].replace("F", "0").replace("B", "1");
+        let col_binary = boardingpass[7..].replace("L", "0").replace("R", "1");
+        let seat = Seat {
+            row: usize::from_str_radix(&row_binary, 2).expect("Row should be binary"),
+            column: usize::from_str_radix(&col_binary, 2).expect("Column should be binary"),
+        };
+        Ok(seat)
+    }
+
+    pub fn to_boardingpass(&self) -> String {
+        let row = (0..7).rev().map(|bit| if self.row & (1 << bit) == 0 { 'F' } else { 'B' });
+        let column = (0..3).rev().map(|bit| if self.column & (1 << bit) == 0 { 'L' } else { 'R' });
+        row.chain(column).collect()
+    }
+
+    pub fn seat_id(&self) -> usize {
+        self.row * 8 + self.column
+    }
+}
+
+pub fn parse_boardingpass(mut seats: Vec<Seat>, line: String) -> Result<Vec<Seat>, ParseError> {
+    // Every line is one seat
+    let line_nr = seats.len() + 1;
+    seats.push(Seat::from_boardingpass(&line).map_err(|e| e.at_line(line_nr))?);
+    Ok(seats)
+}
+
+pub fn star_one(seats: &[Seat]) -> usize {
+    seats.iter().fold(0, |s, x| {
+        if x.seat_id() > s { x.seat_id() } else { s }
+    })
+}
+
+#[derive(Debug)]
+pub struct NoSeatFound;
+
+pub fn star_two(seats: &mut [Seat]) -> Result<usize, NoSeatFound> {
+    seats.sort_by_key(|a| a.seat_id());
+    let mut last_id: usize = 0;
+    for seat in seats {
+        if (last_id != 0) && (last_id == seat.seat_id() - 2) {
+            return Ok(seat.seat_id() - 1);
+        }
+        last_id = seat.seat_id();
+    }
+
+    Err(NoSeatFound)
+}
+
+/// The boarding passes of a full flight of `size` passengers (at most 1000):
+/// a run of consecutive seat IDs with one seat missing in the middle.
+pub fn generate(rng: &mut Random, size: usize) -> Generated {
+    let nr_seats = size.clamp(2, 1000) + 1;
+    // The first and last rows do not exist on this aircraft
+    let first = rng.gen_range(8..=1016 - nr_seats);
+    let mine = rng.gen_range(first + 1..first + nr_seats - 1);
+    let mut seats: Vec<usize> = (first..first + nr_seats).filter(|&id| id != mine).collect();
+    seats.shuffle(rng);
+
+    let lines: Vec<String> = seats
+        .iter()
+        .map(|id| Seat { row: id / 8, column: id % 8 }.to_boardingpass())
+        .collect();
+
+    Generated {
+        input: lines.join("\n"),
+        answer1: Some((first + nr_seats - 1).to_string()),
+        answer2: Some(mine.to_string()),
+    }
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    type Puzzle = Vec<Seat>;
+
+    fn parse_lines(mut lines: impl Iterator<Item = String>) -> Result<Self::Puzzle, ParseError> {
+        lines.try_fold(vec![], parse_boardingpass)
+    }
+
+    fn part1(seats: &Self::Puzzle) -> Option<String> {
+        Some(star_one(seats).to_string())
+    }
+
+    fn part2(seats: &Self::Puzzle) -> Option<String> {
+        let mut seats = seats.to_vec();
+        star_two(&mut seats).ok().map(|x| x.to_string())
+    }
+
+    fn generate(rng: &mut Random, size: usize) -> Option<Generated> {
+        Some(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::Seat;
+
+    static TEST_DATA: &str = "FBFBBFFRLR
+BFFFBBFRRR
+FFFBBBFRRR
+BBFFBBFRLL";
+
+    #[test]
+    fn test_star_one() {
+        let seats = TEST_DATA
+            .lines()
+            .map(|x| x.to_string())
+            .try_fold(
+                vec![],
+                super::parse_boardingpass,
+            )
+            .expect("Invalid data in input file");
+
+        assert_eq!(seats.len(), 4);
+        assert_eq!(seats[0].row, 44);
+        assert_eq!(seats[0].column, 5);
+        assert_eq!(seats[0].seat_id(), 357);
+
+        assert_eq!(seats[1].row, 70);
+        assert_eq!(seats[1].column, 7);
+        assert_eq!(seats[1].seat_id(), 567);
+
+        assert_eq!(seats[2].row, 14);
+        assert_eq!(seats[2].column, 7);
+        assert_eq!(seats[2].seat_id(), 119);
+
+        assert_eq!(seats[3].row, 102);
+        assert_eq!(seats[3].column, 4);
+        assert_eq!(seats[3].seat_id(), 820);
+    }
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..5 {
+            crate::generate::check::<super::Day05>(seed, 500);
+        }
+        crate::generate::check::<super::Day05>(0, 1000);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_seat_round_trip(row in 0..128usize, column in 0..8usize) {
+            let boardingpass = Seat { row, column }.to_boardingpass();
+            let seat = Seat::from_boardingpass(&boardingpass).expect("Invalid boarding pass");
+            prop_assert_eq!((seat.row, seat.column), (row, column));
+        }
+
+        #[test]
+        fn prop_boardingpass_round_trip(boardingpass in "[FB]{7}[LR]{3}") {
+            let seat = Seat::from_boardingpass(&boardingpass).expect("Invalid boarding pass");
+            prop_assert_eq!(seat.to_boardingpass(), boardingpass);
+            prop_assert_eq!(seat.seat_id(), seat.row * 8 + seat.column);
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use std::collections::HashSet;
+use std::vec::Vec;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::error::ParseError;
+use crate::generate::{Generated, Random};
+use crate::solution::Solution;
+
+pub fn star_one<I>(iter: I) -> usize
+where
+    I: Iterator<Item = String>,
+{
+    let mut group_nr: usize = 0;
+    let mut group_answers: Vec<String> = vec![];
+    let ans = iter
+        .map(|x| {
+            if x.is_empty() {
+                group_nr += 1
+            }
+            (group_nr, x)
+        })
+        .filter(|x| !x.1.is_empty())
+        .collect::<Vec<(usize, String)>>()
+        .iter()
+        .fold(&mut group_answers, |s, val| {
+            if s.get_mut(val.0).is_some() {
+                s[val.0] = String::from(&s[val.0]) + &val.1;
+            } else {
+                s.push(String::from(&val.1));
+            }
+
+            s
+        })
+        .iter()
+        .fold(0, |s, x| {
+            let mut ch: Vec<char> = x.chars().collect();
+            ch.sort();
+            ch.dedup();
+            s + ch.len()
+        });
+
+    ans
+}
+
+pub fn star_two<I>(iter: I) -> usize
+where
+    I: Iterator<Item = String>,
+{
+    let mut group_nr: usize = 0;
+    let group_answers: Vec<(usize, String)> = iter
+        .map(|x| {
+            if x.is_empty() {
+                group_nr += 1
+            }
+            (group_nr, x)
+        })
+        .filter(|x| !x.1.is_empty())
+        .collect();
+
+    let mut group_hash: Vec<HashSet<char>> = vec![];
+    for (g, v) in group_answers {
+        match group_hash.get(g) {
+            None => group_hash.push(v.chars().collect()),
+            _ => group_hash[g] = group_hash[g].intersection(&v.chars().collect()).copied().collect()
+        }
+    }
+
+    group_hash.iter().fold(0, |s, x| {
+        s + x.len()
+    })
+}
+
+/// The answers of `size` groups of one to five people, who all answered
+/// "yes" to a few questions and also to a few of their own.
+pub fn generate(rng: &mut Random, size: usize) -> Generated {
+    let questions: Vec<char> = ('a'..='z').collect();
+    let mut groups = vec![];
+    let mut anyone = 0;
+    let mut everyone = 0;
+    for _ in 0..size {
+        let nr_common = rng.gen_range(0..=5);
+        let common: Vec<char> = questions.choose_multiple(rng, nr_common).copied().collect();
+        let mut people: Vec<HashSet<char>> = vec![];
+        for _ in 0..rng.gen_range(1..=5) {
+            let mut answers: HashSet<char> = common.iter().copied().collect();
+            let nr_own = rng.gen_range(0..=5);
+            answers.extend(questions.choose_multiple(rng, nr_own));
+            if answers.is_empty() {
+                answers.insert(*questions.choose(rng).unwrap());
+            }
+            people.push(answers);
+        }
+
+        let union: HashSet<char> = people.iter().flatten().copied().collect();
+        let intersection = union.iter().filter(|q| people.iter().all(|p| p.contains(q))).count();
+        anyone += union.len();
+        everyone += intersection;
+
+        let lines: Vec<String> = people
+            .into_iter()
+            .map(|answers| {
+                let mut answers: Vec<char> = answers.into_iter().collect();
+                answers.sort_unstable();
+                answers.shuffle(rng);
+                answers.into_iter().collect()
+            })
+            .collect();
+        groups.push(lines.join("\n"));
+    }
+
+    Generated {
+        input: groups.join("\n\n"),
+        answer1: Some(anyone.to_string()),
+        answer2: Some(everyone.to_string()),
+    }
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    type Puzzle = Vec<String>;
+
+    fn parse_lines(lines: impl Iterator<Item = String>) -> Result<Self::Puzzle, ParseError> {
+        lines
+            .enumerate()
+            .map(|(i, line)| match line.chars().position(|c| !c.is_ascii_lowercase()) {
+                Some(pos) => Err(ParseError::new(Day06::DAY, &line, "answers should be lowercase letters")
+                    .at_line(i + 1)
+                    .at_column(pos + 1)),
+                None => Ok(line),
+            })
+            .collect()
+    }
+
+    fn part1(lines: &Self::Puzzle) -> Option<String> {
+        Some(star_one(lines.iter().cloned()).to_string())
+    }
+
+    fn part2(lines: &Self::Puzzle) -> Option<String> {
+        Some(star_two(lines.iter().cloned()).to_string())
+    }
+
+    fn generate(rng: &mut Random, size: usize) -> Option<Generated> {
+        Some(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    static TEST_DATA: &str = "abc
+
+a
+b
+c
+
+ab
+ac
+
+a
+a
+a
+a
+
+b";
+
+    #[test]
+    fn test_star_one() {
+        let lines = TEST_DATA.lines().map(String::from);
+        let ans = super::star_one(lines);
+        assert_eq!(ans, 11);
+    }
+
+    #[test]
+    fn test_star_two() {
+        let lines = TEST_DATA.lines().map(String::from);
+        let ans = super::star_two(lines);
+        assert_eq!(ans, 6);
+    }
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..5 {
+            crate::generate::check::<super::Day06>(seed, 100);
+        }
+    }
+}
@@ -0,0 +1,370 @@
+use core::fmt::Debug;
+use std::collections::{HashMap, HashSet};
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::error::ParseError;
+use crate::generate::{Generated, Random};
+use crate::solution::Solution;
+
+#[derive(Debug)]
+pub struct Bag {
+    pub name: String,
+    pub contents: HashMap<String, usize>,
+}
+
+fn until_err<T, E>(err: &mut &mut Result<(), E>, item: Result<T, E>) -> Option<T> {
+    match item {
+        Ok(item) => Some(item),
+        Err(e) => {
+            **err = Err(e);
+            None
+        }
+    }
+}
+
+impl Bag {
+    pub fn from_specification(specification: &str) -> Result<Bag, ParseError> {
+        let invalid = |text: &str, reason: &str| ParseError::new(Day07::DAY, text, reason);
+
+        // Split on "bags contain" for the name and the contents
+        let mut split = specification.split(" bags contain ");
+        let name = match split.next() {
+            Some(first) => String::from(first),
+            None => return Err(invalid(specification, "missing bag name")),
+        };
+        let contents_spec = match split.next() {
+            Some(second) => second,
+            None => return Err(invalid(specification, "missing 'bags contain'")),
+        };
+        if split.next().is_some() {
+            return Err(invalid(specification, "more than one 'bags contain'"));
+        }
+
+        // Split contents on "," and trim leading/trailing whitespace and fluff to get the list of contents
+        let mut err = Ok(());
+        let contents_vec: Vec<(String, usize)> = contents_spec
+            .split(',')
+            .map(|spec| {
+                spec.trim()
+                    .trim_end_matches('.')
+                    .trim_end_matches('s')
+                    .trim_end_matches(" bag")
+            })
+            .map(|spec| match spec {
+                "no other" => Ok((String::default(), 0)),
+                _ => match spec.find(' ') {
+                    Some(idx) => match spec[..idx].parse::<usize>() {
+                        Ok(v) => Ok((String::from(&spec[idx + 1..]), v)),
+                        Err(_) => Err(invalid(spec, "invalid bag count")),
+                    },
+                    None => Err(invalid(spec, "missing bag count")),
+                },
+            })
+            .scan(&mut err, until_err)
+            .collect();
+        err?;
+
+        let bag = Bag {
+            name,
+            contents: contents_vec.into_iter().filter(|x| x.1 != 0).collect(),
+        };
+        Ok(bag)
+    }
+}
+
+pub fn parse_bag_specification(
+    mut bags: HashMap<String, Bag>,
+    line: String,
+) -> Result<HashMap<String, Bag>, ParseError> {
+    let bag = Bag::from_specification(&line)?;
+    let index = String::from(&bag.name);
+    if let Some(b) = bags.get_mut(&index) {
+        *b = bag;
+    } else {
+        bags.insert(index, bag);
+    }
+    Ok(bags)
+}
+
+pub fn can_contain(bag_name: &str, name: &str, bags: &HashMap<String, Bag>) -> bool {
+    can_contain_unseen(bag_name, name, bags, &mut HashSet::new())
+}
+
+/// Looks into every bag only once, so bags that (eventually) hold themselves do not loop forever.
+fn can_contain_unseen<'a>(
+    bag_name: &str,
+    name: &str,
+    bags: &'a HashMap<String, Bag>,
+    seen: &mut HashSet<&'a str>,
+) -> bool {
+    let bag = match bags.get(bag_name) {
+        Some(bag) => bag,
+        None => return false,
+    };
+    if !seen.insert(&bag.name) {
+        return false;
+    }
+    if bag.contents.contains_key(name) {
+        return true;
+    }
+    for subbag_name in bag.contents.keys() {
+        if can_contain_unseen(subbag_name, name, bags, seen) {
+            return true;
+        }
+    }
+
+    false
+}
+
+pub fn star_one(bags: &HashMap<String, Bag>) -> usize {
+    bags.iter()
+        .fold(0_usize, |s, (_, b)| if can_contain(&b.name, "shiny gold", bags) { s + 1 } else { s })
+}
+
+/// The number of bags including the outer one, `None` when a bag ends up inside itself or there are too many.
+pub fn count_contained(bag_name: &str, bags: &HashMap<String, Bag>) -> Option<usize> {
+    count_contained_outside(bag_name, bags, &mut HashSet::new())
+}
+
+/// Keeps the bags around the current one in `outside`, to notice one of them turning up again.
+fn count_contained_outside<'a>(
+    bag_name: &str,
+    bags: &'a HashMap<String, Bag>,
+    outside: &mut HashSet<&'a str>,
+) -> Option<usize> {
+    let bag = match bags.get(bag_name) {
+        Some(bag) => bag,
+        None => return Some(0),
+    };
+    if !outside.insert(&bag.name) {
+        return None;
+    }
+
+    let total = bag.contents.iter().try_fold(1_usize, |total, (name, count)| {
+        total.checked_add(count.checked_mul(count_contained_outside(name, bags, outside)?)?)
+    });
+    outside.remove(bag.name.as_str());
+    total
+}
+
+/// The number of bags inside a shiny gold bag, `None` without a rule for it or when it never ends.
+pub fn star_two(bags: &HashMap<String, Bag>) -> Option<usize> {
+    // Without the outer bag itself, which is not there when it has no rule
+    count_contained("shiny gold", bags)?.checked_sub(1)
+}
+
+const ADJECTIVES: [&str; 20] = [
+    "light", "dark", "bright", "muted", "shiny", "faded", "dotted", "vibrant", "dull", "pale",
+    "clear", "drab", "dim", "mirrored", "plaid", "posh", "striped", "wavy", "dusty", "vivid",
+];
+const COLORS: [&str; 20] = [
+    "red", "orange", "white", "yellow", "gold", "olive", "plum", "blue", "black", "green",
+    "tan", "teal", "violet", "lime", "crimson", "maroon", "salmon", "silver", "indigo", "beige",
+];
+
+/// The rules for `size` bag colors (at most a few hundred), shiny gold among
+/// them. Bags only hold bags from deeper levels, so nothing holds itself.
+pub fn generate(rng: &mut Random, size: usize) -> Generated {
+    let mut names: Vec<String> = vec![];
+    for adjective in ADJECTIVES.iter() {
+        for color in COLORS.iter() {
+            let name = format!("{} {}", adjective, color);
+            if name != "shiny gold" {
+                names.push(name);
+            }
+        }
+    }
+    names.shuffle(rng);
+    names.truncate(size.clamp(2, names.len()) - 1);
+    names.push(String::from("shiny gold"));
+
+    // Shiny gold is somewhere in the middle
+    let nr_levels = 6;
+    let mut levels: Vec<usize> = names.iter().map(|_| rng.gen_range(0..nr_levels)).collect();
+    let gold = names.len() - 1;
+    levels[gold] = rng.gen_range(1..nr_levels - 1);
+
+    // Fill the bags from the deepest level up, keeping count of how many
+    // bags end up inside each of them and which can hold shiny gold
+    let mut order: Vec<usize> = (0..names.len()).collect();
+    order.sort_by_key(|&i| std::cmp::Reverse(levels[i]));
+    let mut inside = vec![0_usize; names.len()];
+    let mut holds_gold = vec![false; names.len()];
+    let mut lines = vec![];
+    for &bag in order.iter() {
+        let deeper: Vec<usize> = (0..names.len()).filter(|&i| levels[i] > levels[bag]).collect();
+        let mut contents = vec![];
+        let nr_inner = rng.gen_range(0..=4);
+        let chosen: Vec<usize> = deeper.choose_multiple(rng, nr_inner).copied().collect();
+        for inner in chosen {
+            let count = rng.gen_range(1..=5);
+            // Keep the total in the millions at most
+            if inside[bag] + count * (inside[inner] + 1) > 1_000_000 {
+                continue;
+            }
+            inside[bag] += count * (inside[inner] + 1);
+            holds_gold[bag] |= inner == gold || holds_gold[inner];
+            let plural = if count == 1 { "" } else { "s" };
+            contents.push(format!("{} {} bag{}", count, names[inner], plural));
+        }
+
+        if contents.is_empty() {
+            lines.push(format!("{} bags contain no other bags.", names[bag]));
+        } else {
+            lines.push(format!("{} bags contain {}.", names[bag], contents.join(", ")));
+        }
+    }
+    lines.shuffle(rng);
+
+    Generated {
+        input: lines.join("\n"),
+        answer1: Some(holds_gold.iter().filter(|&&x| x).count().to_string()),
+        answer2: Some(inside[gold].to_string()),
+    }
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    type Puzzle = HashMap<String, Bag>;
+
+    fn parse_lines(lines: impl Iterator<Item = String>) -> Result<Self::Puzzle, ParseError> {
+        lines
+            .enumerate()
+            .try_fold(HashMap::new(), |bags, (i, line)| {
+                parse_bag_specification(bags, line).map_err(|e| e.at_line(i + 1))
+            })
+    }
+
+    fn part1(bags: &Self::Puzzle) -> Option<String> {
+        Some(star_one(bags).to_string())
+    }
+
+    fn part2(bags: &Self::Puzzle) -> Option<String> {
+        star_two(bags).map(|count| count.to_string())
+    }
+
+    fn generate(rng: &mut Random, size: usize) -> Option<Generated> {
+        Some(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    static TEST_DATA_STAR_ONE: &str = "light red bags contain 1 bright white bag, 2 muted yellow bags.
+dark orange bags contain 3 bright white bags, 4 muted yellow bags.
+bright white bags contain 1 shiny gold bag.
+muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+faded blue bags contain no other bags.
+dotted black bags contain no other bags.";
+
+    #[test]
+    fn test_star_one() {
+        let bags = TEST_DATA_STAR_ONE
+            .lines()
+            .map(|x| x.to_string())
+            .try_fold(HashMap::new(), super::parse_bag_specification)
+            .expect("Invalid data in input file");
+
+        assert_eq!(bags.len(), 9);
+
+        assert!(bags.contains_key("light red"));
+        let bag = bags.get("light red").unwrap();
+        assert_eq!(bag.name, "light red");
+        assert_eq!(bag.contents.len(), 2);
+        assert!(bag.contents.contains_key("bright white"));
+        assert!(bag.contents.contains_key("muted yellow"));
+        let amount1 = bag.contents.get("bright white").unwrap();
+        assert_eq!(amount1, &1_usize);
+        let amount2 = bag.contents.get("muted yellow").unwrap();
+        assert_eq!(amount2, &2_usize);
+
+        assert!(bags.contains_key("faded blue"));
+        let bag = bags.get("faded blue").unwrap();
+        assert_eq!(bag.name, "faded blue");
+        assert_eq!(bag.contents.len(), 0);
+
+        assert!(super::can_contain("light red", "bright white", &bags));
+        assert!(super::can_contain("light red", "shiny gold", &bags));
+        assert!(super::can_contain("light red", "dotted black", &bags));
+        assert!(!super::can_contain("faded blue", "dotted black", &bags));
+
+        assert_eq!(super::star_one(&bags), 4);
+    }
+
+    static TEST_DATA_STAR_TWO: &str = "shiny gold bags contain 2 dark red bags.
+dark red bags contain 2 dark orange bags.
+dark orange bags contain 2 dark yellow bags.
+dark yellow bags contain 2 dark green bags.
+dark green bags contain 2 dark blue bags.
+dark blue bags contain 2 dark violet bags.
+dark violet bags contain no other bags.";
+
+    #[test]
+    fn test_star_two() {
+        let mut bags = TEST_DATA_STAR_TWO
+            .lines()
+            .map(|x| x.to_string())
+            .try_fold(HashMap::new(), super::parse_bag_specification)
+            .expect("Invalid data in input file");
+
+        assert_eq!(bags.len(), 7);
+
+        assert!(bags.contains_key("dark red"));
+        let bag = bags.get("dark red").unwrap();
+        assert_eq!(bag.name, "dark red");
+        assert_eq!(bag.contents.len(), 1);
+        assert!(bag.contents.contains_key("dark orange"));
+        let amount = bag.contents.get("dark orange").unwrap();
+        assert_eq!(amount, &2_usize);
+
+        assert!(super::can_contain("shiny gold", "dark red", &bags));
+
+        assert_eq!(super::star_two(&bags), Some(126));
+        bags.remove("shiny gold");
+        assert_eq!(super::star_two(&bags), None);
+    }
+
+    #[test]
+    fn test_holds_itself() {
+        let parse = |input: &str| {
+            input.lines()
+                .map(|x| x.to_string())
+                .try_fold(HashMap::new(), super::parse_bag_specification)
+                .expect("Invalid data in input file")
+        };
+
+        let bags = parse("shiny gold bags contain 1 shiny gold bag.");
+        assert!(super::can_contain("shiny gold", "shiny gold", &bags));
+        assert_eq!(super::star_one(&bags), 1);
+        assert_eq!(super::star_two(&bags), None);
+
+        let bags = parse("shiny gold bags contain 2 dark red bags.
+dark red bags contain 1 dark blue bag, 3 faded blue bags.
+dark blue bags contain 1 dark red bag.
+faded blue bags contain no other bags.");
+        assert!(!super::can_contain("dark red", "shiny gold", &bags));
+        assert_eq!(super::star_one(&bags), 0);
+        assert_eq!(super::star_two(&bags), None);
+
+        // Two ways to the same bag are not a loop
+        let bags = parse("shiny gold bags contain 2 dark red bags, 1 dark blue bag.
+dark red bags contain 1 dark blue bag.
+dark blue bags contain no other bags.");
+        assert_eq!(super::star_two(&bags), Some(5));
+    }
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..5 {
+            crate::generate::check::<super::Day07>(seed, 200);
+        }
+    }
+}
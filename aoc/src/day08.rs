@@ -0,0 +1,407 @@
+use log::{debug, error, log_enabled, Level};
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::error::ParseError;
+use crate::flow;
+use crate::generate::{Generated, Random};
+use crate::solution::{Solution, Variant};
+pub use crate::vm::{Fault, Instruction, Operation, Program, CPU};
+
+pub fn star_one(program: &Program) -> Result<isize, Fault> {
+    let mut cpu = CPU::new(program.clone());
+    // The loop is only logged, so it only needs a trace when it is
+    if log_enabled!(Level::Debug) {
+        cpu = cpu.recording();
+    }
+    cpu.run_program_until_loop()?;
+    if let Some(cycle) = cpu.cycle() {
+        debug!("Loop of {} instructions:", cycle.len());
+        for step in cycle {
+            debug!("{}", step);
+        }
+    }
+    Ok(cpu.accumulator())
+}
+
+/// Run the program, trying every flip on the way in a fork of the CPU, which
+/// does not record a trace so that forking stays cheap. A fork that jumps out
+/// of the program is a flip that does not work; other faults are returned.
+pub fn run_possible_mods(cpu: &mut CPU, is_modded: bool) -> Result<(bool, isize, usize), Fault> {
+    let mut instructions_ran: usize = 0;
+    loop {
+        match (&cpu.program.instructions[cpu.instruction_pointer].operation, is_modded) {
+            (_, true) => {},
+            (Operation::Jmp, false) | (Operation::Nop, false) => {
+                // Fork state
+                let mut modded_cpu = cpu.clone();
+                flip(&mut modded_cpu.program.instructions[cpu.instruction_pointer]);
+                match run_possible_mods(&mut modded_cpu, true) {
+                    Ok((done, result, branch_instructions_ran)) => {
+                        instructions_ran += branch_instructions_ran;
+                        if done {
+                            return Ok((done, result, instructions_ran));
+                        }
+                    }
+                    Err(Fault::JumpOutOfBounds) => (),
+                    Err(fault) => return Err(fault),
+                }
+            },
+            (_, false) => {}
+        }
+
+        cpu.single_step()?;
+        instructions_ran += 1;
+
+        if cpu.terminated() {
+            break;
+        }
+
+        if cpu.visited() {
+            return Ok((false, cpu.accumulator(), instructions_ran));
+        }
+    }
+
+    Ok((true, cpu.accumulator(), instructions_ran))
+}
+
+/// The accumulator after the program ends with one `jmp` or `nop` flipped,
+/// and the instructions it took to find out; `None` if no flip repairs it.
+pub fn star_two(program: &Program) -> Result<Option<(isize, usize)>, Fault> {
+    let mut cpu = CPU::new(program.clone());
+    let (done, result, instructions_ran) = run_possible_mods(&mut cpu, false)?;
+    if !done {
+        return Ok(None);
+    }
+
+    Ok(Some((result, instructions_ran)))
+}
+
+pub fn star_two_original(program: &Program) -> Result<Option<(isize, usize)>, Fault> {
+    let mut instructions_ran: usize = 0;
+    let mut modded = program.clone();
+    let mut modded_ip = 0;
+    loop {
+        let mut cpu = CPU::new(modded);
+        // Jumping out of the program does not fix it either
+        let done = match cpu.run_program_until_loop() {
+            Err(Fault::JumpOutOfBounds) => false,
+            result => result?,
+        };
+        instructions_ran += cpu.visited.iter().filter(|x| **x).count();
+        if done {
+            return Ok(Some((cpu.accumulator(), instructions_ran)));
+        }
+
+        // Mod next instruction in sequence (brute force)
+        let next = program.instructions[modded_ip..]
+            .iter()
+            .position(|instruction| instruction.operation == Operation::Jmp || instruction.operation == Operation::Nop);
+        match next {
+            Some(offset) => modded_ip += offset,
+            None => return Ok(None),
+        }
+
+        modded = program.clone();
+        flip(&mut modded.instructions[modded_ip]);
+        modded_ip += 1;
+    }
+}
+
+/// The accumulator after the program ends, with the instruction to flip
+/// found by looking at its control flow instead of running it.
+pub fn star_two_control_flow(program: &Program) -> Result<Option<isize>, Fault> {
+    let index = match flow::repair(program) {
+        Some(index) => index,
+        None => return Ok(None),
+    };
+    let mut fixed = program.clone();
+    flip(&mut fixed.instructions[index]);
+    let mut cpu = CPU::new(fixed);
+    match cpu.run_program_until_loop()? {
+        true => Ok(Some(cpu.accumulator())),
+        false => Ok(None),
+    }
+}
+
+/// An answer for `Solution::part1` or `part2`. A fault is logged, so that it
+/// does not look like the program has no answer.
+fn answer(result: Result<Option<isize>, Fault>) -> Option<String> {
+    match result {
+        Ok(acc) => acc.map(|acc| acc.to_string()),
+        Err(fault) => {
+            error!("Day 8: the program {}", fault);
+            None
+        }
+    }
+}
+
+// Whether the program ends rather than loops or faults, and the accumulator then
+fn run(instructions: &[Instruction]) -> (bool, isize) {
+    let mut cpu = CPU::new(Program { instructions: instructions.to_vec() });
+    let ends = cpu.run_program_until_loop() == Ok(true);
+    (ends, cpu.accumulator())
+}
+
+fn flip(instruction: &mut Instruction) {
+    instruction.operation = match instruction.operation {
+        Operation::Nop => Operation::Jmp,
+        Operation::Jmp => Operation::Nop,
+        other => other,
+    };
+}
+
+/// A boot code of `size` instructions (at least 10) that ends when exactly
+/// one of its `jmp` or `nop` instructions is flipped, and loops otherwise.
+pub fn generate(rng: &mut Random, size: usize) -> Generated {
+    let len = size.max(10);
+    let (instructions, exit) = loop {
+        // Blocks of code ending with a jump to the next block to run. Some
+        // are never run, among them the last one so that no flip can make
+        // the program fall off its end.
+        let mut starts = vec![0];
+        while let Some(&last) = starts.last() {
+            let next = last + rng.gen_range(1..=4);
+            if next >= len {
+                break;
+            }
+            starts.push(next);
+        }
+        let nr_blocks = starts.len();
+        let live: Vec<bool> = (0..nr_blocks).map(|b| b == 0 || (b < nr_blocks - 1 && rng.gen_bool(0.7))).collect();
+
+        // The last block to run jumps out of the program. Flipping that jump
+        // makes it fall through into the next block, preferably one that
+        // never ran so the loop takes a detour before jumping back. That block
+        // is not the last one, or flipping its jump would leave the program
+        // as well.
+        let mut exits: Vec<usize> = (1..nr_blocks - 2).filter(|&b| live[b] && !live[b + 1]).collect();
+        if exits.is_empty() {
+            exits = (1..nr_blocks - 2).filter(|&b| live[b]).collect();
+        }
+        let exit = match exits.choose(rng) {
+            Some(&exit) => exit,
+            None => continue,
+        };
+        let mut order: Vec<usize> = (1..nr_blocks).filter(|&b| live[b] && b != exit).collect();
+        order.shuffle(rng);
+        order.insert(0, 0);
+        order.push(exit);
+
+        let mut instructions = vec![];
+        for i in 0..len {
+            let instruction = if rng.gen_bool(0.7) {
+                Instruction::new(Operation::Acc, rng.gen_range(-50..=50))
+            } else {
+                // Even flipped, a nop never jumps out of the program
+                Instruction::new(Operation::Nop, rng.gen_range(0..len) as isize - i as isize)
+            };
+            instructions.push(instruction);
+        }
+        let live_starts: Vec<usize> = order.iter().map(|&b| starts[b]).collect();
+        for block in 0..nr_blocks {
+            let end = starts.get(block + 1).copied().unwrap_or(len) - 1;
+            let target = match order.iter().position(|&b| b == block) {
+                Some(i) => order.get(i + 1).map(|&next| starts[next]).unwrap_or(len),
+                None => *live_starts.choose(rng).unwrap(),
+            };
+            instructions[end] = Instruction::new(Operation::Jmp, target as isize - end as isize);
+        }
+        break (instructions, starts[exit + 1] - 1);
+    };
+    let (ends, fixed) = run(&instructions);
+    assert!(ends, "Generated program does not end");
+
+    let mut corrupted = instructions;
+    flip(&mut corrupted[exit]);
+    let (ends, looped) = run(&corrupted);
+    assert!(!ends, "Corrupted program still ends");
+    let nr_fixes = (0..len)
+        .filter(|&i| {
+            let mut attempt = corrupted.clone();
+            flip(&mut attempt[i]);
+            corrupted[i].operation != Operation::Acc && run(&attempt).0
+        })
+        .count();
+    assert_eq!(nr_fixes, 1, "Corrupted program has more than one fix");
+
+    let lines: Vec<String> = corrupted
+        .iter()
+        .map(|instruction| instruction.to_string())
+        .collect();
+
+    Generated {
+        input: lines.join("\n"),
+        answer1: Some(looped.to_string()),
+        answer2: Some(fixed.to_string()),
+    }
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+    type Puzzle = Program;
+
+    fn parse_lines(lines: impl Iterator<Item = String>) -> Result<Self::Puzzle, ParseError> {
+        let mut program = Program {
+            instructions: vec![],
+        };
+        for (line_nr, line) in (1..).zip(lines) {
+            program.add_instruction(&line)?;
+            // The handheld of the puzzle knows nothing of the other operations
+            let operation = program.instructions[line_nr - 1].operation;
+            if !matches!(operation, Operation::Nop | Operation::Acc | Operation::Jmp) {
+                return Err(ParseError::new(Self::DAY, &line, "unknown operation").at_line(line_nr).at_column(1));
+            }
+        }
+        if program.instructions.is_empty() {
+            return Err(ParseError::new(Self::DAY, "", "empty program"));
+        }
+
+        Ok(program)
+    }
+
+    fn part1(program: &Self::Puzzle) -> Option<String> {
+        answer(star_one(program).map(Some))
+    }
+
+    fn part2(program: &Self::Puzzle) -> Option<String> {
+        answer(star_two(program).map(|fixed| fixed.map(|(acc_value_after_exit, _)| acc_value_after_exit)))
+    }
+
+    fn variants(part: u8) -> Vec<Variant<Self::Puzzle>> {
+        let variants: Vec<Variant<Self::Puzzle>> = match part {
+            1 => vec![("default", Self::part1)],
+            _ => vec![
+                ("dfs", Self::part2),
+                ("brute", |program| answer(star_two_original(program).map(|fixed| fixed.map(|(acc, _)| acc)))),
+                ("cfg", |program| answer(star_two_control_flow(program))),
+            ],
+        };
+        variants
+    }
+
+    fn generate(rng: &mut Random, size: usize) -> Option<Generated> {
+        Some(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    static TEST_DATA: &str = "nop +0
+acc +1
+jmp +4
+acc +3
+jmp -3
+acc -99
+acc +1
+jmp -4
+acc +6";
+
+    #[test]
+    fn test_star_one() {
+        let mut program = super::Program {
+            instructions: vec![],
+        };
+        for line in TEST_DATA.lines().map(|x| x.to_string()) {
+            program
+                .add_instruction(&line)
+                .expect("Invalid data in input file");
+        }
+
+        assert_eq!(program.instructions.len(), 9);
+        assert_eq!(program.instructions[0].operation, super::Operation::Nop);
+        assert_eq!(program.instructions[1].operation, super::Operation::Acc);
+        assert_eq!(program.instructions[1], super::Instruction::new(super::Operation::Acc, 1));
+
+        let result = super::star_one(&program);
+        assert_eq!(result, Ok(5));
+    }
+
+    #[test]
+    fn test_star_two() {
+        let mut program = super::Program {
+            instructions: vec![],
+        };
+        for line in TEST_DATA.lines().map(|x| x.to_string()) {
+            program
+                .add_instruction(&line)
+                .expect("Invalid data in input file");
+        }
+
+        assert_eq!(program.instructions.len(), 9);
+        assert_eq!(program.instructions[0].operation, super::Operation::Nop);
+        assert_eq!(program.instructions[1].operation, super::Operation::Acc);
+        assert_eq!(program.instructions[1], super::Instruction::new(super::Operation::Acc, 1));
+
+        let (result, _) = super::star_two(&program).expect("Program faulted").expect("No flip found");
+        assert_eq!(result, 8);
+        assert_eq!(super::star_two_control_flow(&program), Ok(Some(8)));
+    }
+
+    #[test]
+    fn test_faults() {
+        use crate::solution::Solution;
+
+        // Flipping the jump that leaves the program fixes it, for every variant
+        let program = super::Day08::parse("jmp -1\nacc +1\n").expect("Invalid program");
+        assert_eq!(super::star_one(&program), Err(super::Fault::JumpOutOfBounds));
+        assert_eq!(super::Day08::part1(&program), None);
+        for (name, variant) in super::Day08::variants(2) {
+            assert_eq!(variant(&program), Some(String::from("1")), "{}", name);
+        }
+
+        let program =
+            super::Day08::parse(&format!("acc +{}\nacc +1\njmp -2\n", isize::MAX)).expect("Invalid program");
+        assert_eq!(super::star_one(&program), Err(super::Fault::Overflow));
+        assert_eq!(super::Day08::part1(&program), None);
+        for (name, variant) in super::Day08::variants(2) {
+            assert_eq!(variant(&program), None, "{}", name);
+        }
+
+        // No flip gets past the second jmp -1
+        let program = super::Day08::parse("acc +1\njmp -1\njmp -1\n").expect("Invalid program");
+        assert_eq!(super::star_two(&program), Ok(None));
+        assert_eq!(super::star_two_original(&program), Ok(None));
+        for (name, variant) in super::Day08::variants(2) {
+            assert_eq!(variant(&program), None, "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_parse_error() {
+        use crate::solution::Solution;
+
+        let err = super::Day08::parse("nop +0\nacc +1\nfoo +4\n").expect_err("Unknown operation accepted");
+        assert_eq!(err.line, Some(3));
+        assert_eq!(err.column, Some(1));
+        assert_eq!(err.text, "foo +4");
+
+        let err = super::Day08::parse("nop +0\njmp x\n").expect_err("Invalid operand accepted");
+        assert_eq!(err.to_string(), "day 8, line 2, column 5: invalid operand: \"jmp x\"");
+
+        assert!(super::Day08::parse("nop\n").is_err());
+        let err = super::Day08::parse("").expect_err("Empty program accepted");
+        assert_eq!(err.reason, "empty program");
+
+        // Only the operations of the puzzle
+        let err = super::Day08::parse("nop +0\nmul a 2\n").expect_err("Unknown operation accepted");
+        assert_eq!((err.line, err.column, err.reason.as_str()), (Some(2), Some(1), "unknown operation"));
+    }
+
+    #[test]
+    fn test_generate() {
+        use crate::solution::Solution;
+
+        for seed in 0..5 {
+            let generated = crate::generate::check::<super::Day08>(seed, 300);
+            let program = super::Day08::parse(&generated.input).expect("Invalid generated input");
+            let fixed = super::star_two_control_flow(&program).expect("Generated program faulted");
+            assert_eq!(fixed.map(|acc| acc.to_string()), generated.answer2);
+        }
+        for seed in 0..20 {
+            crate::generate::check::<super::Day08>(seed, 1);
+        }
+    }
+}
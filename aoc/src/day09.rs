@@ -0,0 +1,205 @@
+use std::vec::Vec;
+use rand::seq::SliceRandom;
+use rand::Rng;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::error::ParseError;
+use crate::generate::{Generated, Random};
+use crate::solution::Solution;
+
+pub fn find_sum_components(sum: isize, numbers: &[isize]) -> Option<(usize, usize)> {
+    for i in 0..numbers.len() {
+        for j in 0..numbers.len() {
+            if i != j && numbers[i] + numbers[j] == sum {
+                return Some((i, j))
+            }
+        }
+    }
+
+    None
+}
+
+pub fn star_one(numbers: &[isize], window: usize) -> Option<isize> {
+    let mut start = 0_usize;
+    loop {
+        if start + window >= numbers.len() {
+            return None;
+        }
+
+        let possibles: Vec<isize> = numbers[start..start+window].to_vec();
+        let target = numbers[start+window];
+        let indices = find_sum_components(target, &possibles);
+        match indices {
+            Some(_) => start += 1,
+            None => return Some(target),
+        };
+    }
+}
+
+/// The sum of the smallest and largest number in the first range starting at
+/// `i`, at least `range` numbers long, that adds up to `target`.
+fn weakness_from(numbers: &[isize], target: isize, i: usize, range: usize) -> Option<isize> {
+    for j in i+range..numbers.len() {
+        let sum = numbers[i..j].iter().sum::<isize>();
+        if sum == target {
+            let min = numbers[i..j].iter().min().expect("No minimum in range");
+            let max = numbers[i..j].iter().max().expect("No maximum in range");
+            return Some(min + max);
+        }
+    }
+
+    None
+}
+
+pub fn star_two(numbers: &[isize], window: usize) -> Option<isize> {
+    let target = star_one(numbers, window)?;
+    for range in 2..numbers.len() {
+        // The first start that works, also when the starts are tried in parallel
+        #[cfg(not(feature = "parallel"))]
+        let weakness = (0..numbers.len()).find_map(|i| weakness_from(numbers, target, i, range));
+        #[cfg(feature = "parallel")]
+        let weakness = (0..numbers.len()).into_par_iter().find_map_first(|i| weakness_from(numbers, target, i, range));
+        if weakness.is_some() {
+            return weakness;
+        }
+    }
+
+    None
+}
+
+/// XMAS data of `size` numbers (at least 30, at most 1000) after a preamble
+/// of 25, with one number that is not the sum of two of the 25 before it
+/// but is the sum of exactly one contiguous range.
+pub fn generate(rng: &mut Random, size: usize) -> Generated {
+    let window = 25;
+    let len = size.clamp(30, 1000);
+    loop {
+        let mut numbers: Vec<isize> = (1..=50).collect();
+        numbers.shuffle(rng);
+        numbers.truncate(window);
+        let invalid_at = rng.gen_range(window + 2..len);
+
+        let mut range = (0, 0);
+        for i in window..len {
+            let mut previous = numbers[i - window..i].to_vec();
+            if i == invalid_at {
+                let start = rng.gen_range(0..i - 1);
+                let end = rng.gen_range(start + 2..=i.min(start + 17));
+                range = (start, end);
+                numbers.push(numbers[start..end].iter().sum());
+                continue;
+            }
+            // Adding up the smaller ones keeps the numbers from growing too fast
+            previous.sort_unstable();
+            let pair: Vec<&isize> = previous[..10].choose_multiple(rng, 2).collect();
+            numbers.push(pair[0] + pair[1]);
+        }
+
+        let target = numbers[invalid_at];
+        let previous = &numbers[invalid_at - window..invalid_at];
+        let is_sum = previous
+            .iter()
+            .enumerate()
+            .any(|(i, a)| previous.iter().skip(i + 1).any(|b| a + b == target));
+        let mut nr_ranges = 0;
+        for start in 0..len {
+            let mut sum = numbers[start];
+            for x in numbers[start + 1..].iter() {
+                sum += x;
+                if sum == target {
+                    nr_ranges += 1;
+                }
+                if sum >= target {
+                    break;
+                }
+            }
+        }
+        if is_sum || nr_ranges != 1 {
+            continue;
+        }
+
+        let contiguous = &numbers[range.0..range.1];
+        let weakness = contiguous.iter().min().unwrap() + contiguous.iter().max().unwrap();
+        let lines: Vec<String> = numbers.iter().map(|x| x.to_string()).collect();
+        return Generated {
+            input: lines.join("\n"),
+            answer1: Some(target.to_string()),
+            answer2: Some(weakness.to_string()),
+        };
+    }
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+    type Puzzle = Vec<isize>;
+
+    fn parse_lines(lines: impl Iterator<Item = String>) -> Result<Self::Puzzle, ParseError> {
+        crate::parse_number_lines(Self::DAY, lines)
+    }
+
+    fn part1(numbers: &Self::Puzzle) -> Option<String> {
+        star_one(numbers, 25).map(|x| x.to_string())
+    }
+
+    fn part2(numbers: &Self::Puzzle) -> Option<String> {
+        star_two(numbers, 25).map(|x| x.to_string())
+    }
+
+    fn generate(rng: &mut Random, size: usize) -> Option<Generated> {
+        Some(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    static TEST_DATA: &str = "35
+20
+15
+25
+47
+40
+62
+55
+65
+95
+102
+117
+150
+182
+127
+219
+299
+277
+309
+576";
+
+    #[test]
+    fn test_star_one() {
+        let numbers: Vec<isize> = TEST_DATA.lines().map(String::from)
+            .map(|x| x.parse::<isize>().unwrap_or_else(|_| panic!("Invalid number: {}", &x)))
+            .collect();
+        let ans = super::star_one(&numbers, 5).expect("No answer found");
+        assert_eq!(ans, 127);
+    }
+
+    #[test]
+    fn test_star_two() {
+        let numbers: Vec<isize> = TEST_DATA.lines().map(String::from)
+            .map(|x| x.parse::<isize>().unwrap_or_else(|_| panic!("Invalid number: {}", &x)))
+            .collect();
+        let ans = super::star_two(&numbers, 5).expect("No answer found");
+        assert_eq!(ans, 62);
+        assert_eq!(super::star_two(&numbers[..5], 5), None);
+        assert_eq!(super::star_two(&[], 5), None);
+    }
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..5 {
+            crate::generate::check::<super::Day09>(seed, 60);
+        }
+    }
+}
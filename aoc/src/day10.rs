@@ -0,0 +1,271 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::vec::Vec;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::error::ParseError;
+use crate::generate::{Generated, Random};
+use crate::int::{self, Int, Overflow};
+use crate::solution::Solution;
+
+fn find_complete_steps(joltages: &[isize]) -> (isize, isize) {
+    let mut joltages: Vec<isize> = joltages.to_vec();
+    joltages.sort_unstable();
+    joltages.iter().fold((0, 0), |(one, three), joltage| {
+        match joltage - (one + three * 3) {
+            1 => (one + 1, three),
+            3 => (one, three + 1),
+            _ => panic!("Invalid next value {}", joltage),
+        }
+    })
+}
+
+pub fn star_one(joltages: &[isize]) -> isize {
+    let (onesteps, threesteps) = find_complete_steps(joltages);
+    onesteps * (threesteps + 1)
+}
+
+pub fn star_two(joltages: &[isize]) -> Result<Int, Overflow> {
+    let mut joltages: Vec<isize> = joltages.to_vec();
+    joltages.insert(0, 0);
+    joltages.sort_unstable();
+    joltages.push(joltages[joltages.len()-1] + 3);
+
+    // TODO: understand this part ;-D I got to the part where I collected the distances, but then gave up.
+    // Thanks to @johnny from MNOT for the working algorithm.
+    // Something to do with the amount of choices given consecutive single steps? But why?
+    //    base = (nr_ones ^ 2 - nr_ones + 2) / 2
+    //    result = multiply result of above formula for each length of consecutive ones
+    joltages
+        // Collect distances by subtracting consecutive values
+        .windows(2)
+        .map(|sl| sl[1] - sl[0])
+        .collect::<Vec<isize>>()[..]
+
+        // Split on the 3-distance elements
+        .split(|d| d == &3)
+
+        // Filter out every 3-distance element, and the final empty one
+        .filter(|slice| !slice.is_empty() && slice[0] != 3)
+
+        // Fold into a hashmap with keys the amount of consecutive ones
+        .fold(HashMap::new(), |mut s, d| { *s.entry(isize::try_from(d.len()).unwrap()).or_insert(0) += 1; s })
+
+        // Fold the map into the single result by the base formula above
+        .iter()
+        .try_fold(Int::from(1), |result, (key, value)| {
+            let base = int::from((key.pow(2) - key + 2) / 2)?;
+            let exp = u32::try_from(*value).map_err(|_| Overflow)?;
+            int::mul(&result, &int::pow(&base, exp)?)
+        })
+}
+
+/// Without big integers, the number of arrangements has to stay well within range.
+#[cfg(not(feature = "bigint"))]
+fn within_range(arrangements: &Int) -> bool {
+    *arrangements < 1_000_000_000_000_000
+}
+
+#[cfg(feature = "bigint")]
+fn within_range(_arrangements: &Int) -> bool {
+    true
+}
+
+/// A bag of `size` adapters whose joltages step up by one or three, with at
+/// most four steps of one in a row like in the real bags.
+pub fn generate(rng: &mut Random, size: usize) -> Generated {
+    // Number of arrangements reaching every adapter so far, from the outlet on
+    let mut adapters: Vec<(isize, Int)> = vec![(0, Int::from(1))];
+    let mut ones = 0;
+    let mut threes = 0;
+    let mut run = 0;
+    for _ in 0..size.max(1) {
+        let last = adapters.last().unwrap().0;
+        let arrangements = |joltage: isize| -> Int {
+            adapters
+                .iter()
+                .rev()
+                .take(3)
+                .filter(|(j, _)| joltage - j <= 3)
+                .map(|(_, ways)| ways)
+                .sum()
+        };
+
+        if run < 4 && rng.gen_bool(0.6) && within_range(&arrangements(last + 1)) {
+            adapters.push((last + 1, arrangements(last + 1)));
+            ones += 1;
+            run += 1;
+        } else {
+            adapters.push((last + 3, arrangements(last + 3)));
+            threes += 1;
+            run = 0;
+        }
+    }
+
+    let arrangements = adapters.last().unwrap().1.to_string();
+    let mut joltages: Vec<isize> = adapters.into_iter().skip(1).map(|(joltage, _)| joltage).collect();
+    joltages.shuffle(rng);
+    let lines: Vec<String> = joltages.iter().map(|x| x.to_string()).collect();
+
+    Generated {
+        input: lines.join("\n"),
+        // The device is always three higher than the highest adapter
+        answer1: Some((ones * (threes + 1)).to_string()),
+        answer2: Some(arrangements),
+    }
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    type Puzzle = Vec<isize>;
+
+    /// Both stars count on every adapter being one or three jolts above the next lower one.
+    fn parse_lines(lines: impl Iterator<Item = String>) -> Result<Self::Puzzle, ParseError> {
+        let joltages: Vec<isize> = crate::parse_number_lines(Self::DAY, lines)?;
+        let mut sorted: Vec<(isize, usize)> = joltages.iter().copied().zip(1..).collect();
+        sorted.sort_unstable();
+        let mut previous = 0;
+        for (joltage, line_nr) in sorted {
+            let step = joltage - previous;
+            if step != 1 && step != 3 {
+                let reason = format!("adapter is {} jolts above the next lower one, expected 1 or 3", step);
+                return Err(ParseError::new(Self::DAY, &joltage.to_string(), reason).at_line(line_nr));
+            }
+            previous = joltage;
+        }
+        Ok(joltages)
+    }
+
+    fn part1(joltages: &Self::Puzzle) -> Option<String> {
+        Some(star_one(joltages).to_string())
+    }
+
+    fn part2(joltages: &Self::Puzzle) -> Option<String> {
+        int::answer(Self::DAY, star_two(joltages))
+    }
+
+    fn generate(rng: &mut Random, size: usize) -> Option<Generated> {
+        Some(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    static TEST_DATA_1: &str = "16
+10
+15
+5
+1
+11
+7
+19
+6
+12
+4";
+
+    static TEST_DATA_2: &str = "28
+33
+18
+42
+31
+14
+46
+20
+48
+47
+24
+23
+49
+45
+19
+38
+39
+11
+1
+32
+25
+35
+8
+17
+7
+9
+4
+2
+34
+10
+3";
+
+    #[test]
+    fn test_star_one() {
+        let joltages: Vec<isize> = TEST_DATA_1
+            .lines()
+            .map(String::from)
+            .map(|x| {
+                x.parse::<isize>()
+                    .unwrap_or_else(|_| panic!("Invalid number: {}", &x))
+            })
+            .collect();
+        let ans = super::star_one(&joltages);
+        assert_eq!(ans, 7 * 5);
+
+        let joltages: Vec<isize> = TEST_DATA_2
+            .lines()
+            .map(String::from)
+            .map(|x| {
+                x.parse::<isize>()
+                    .unwrap_or_else(|_| panic!("Invalid number: {}", &x))
+            })
+            .collect();
+        let ans = super::star_one(&joltages);
+        assert_eq!(ans, 22 * 10);
+    }
+
+    #[test]
+    fn test_star_two() {
+        let joltages: Vec<isize> = TEST_DATA_1
+            .lines()
+            .map(String::from)
+            .map(|x| {
+                x.parse::<isize>()
+                    .unwrap_or_else(|_| panic!("Invalid number: {}", &x))
+            })
+            .collect();
+        let ans = super::star_two(&joltages);
+        assert_eq!(ans, Ok(8.into()));
+
+        let joltages: Vec<isize> = TEST_DATA_2
+            .lines()
+            .map(String::from)
+            .map(|x| {
+                x.parse::<isize>()
+                    .unwrap_or_else(|_| panic!("Invalid number: {}", &x))
+            })
+            .collect();
+        let ans = super::star_two(&joltages);
+        assert_eq!(ans, Ok(19208.into()));
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        use crate::Solution;
+
+        let parse = |input: &str| super::Day10::parse_lines(input.lines().map(String::from));
+        assert!(parse(TEST_DATA_1).is_ok());
+        assert_eq!(parse("5").unwrap_err().line, Some(1));
+        assert_eq!(parse("1\n2\n3\n7").unwrap_err().line, Some(4));
+        assert_eq!(parse("4\n1\n4").unwrap_err().line, Some(3));
+        assert!(parse("2").is_err());
+        assert!(parse("0").is_err());
+    }
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..5 {
+            crate::generate::check::<super::Day10>(seed, 100);
+        }
+        crate::generate::check::<super::Day10>(0, 1000);
+    }
+}
@@ -0,0 +1,168 @@
+use std::fmt;
+use std::vec::Vec;
+use rand::Rng;
+
+use crate::automaton::{Dense, Neighbourhood, Rule};
+use crate::error::ParseError;
+use crate::generate::{Generated, Random};
+use crate::grid::Grid;
+use crate::solution::Solution;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum TileState {
+    Floor,
+    Empty,
+    Occupied,
+}
+
+impl From<TileState> for char {
+    fn from(tile: TileState) -> char {
+        match tile {
+            TileState::Floor => '.',
+            TileState::Empty => 'L',
+            TileState::Occupied => '#',
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Ferry {
+    pub map: Grid<TileState>,
+}
+
+impl Ferry {
+    pub fn from_layout<L: AsRef<str>>(lines: impl Iterator<Item = L>) -> Result<Self, ParseError> {
+        let map = Grid::from_lines(Day11::DAY, lines, |ch| match ch {
+            '.' => Ok(TileState::Floor),
+            'L' => Ok(TileState::Empty),
+            '#' => Ok(TileState::Occupied),
+            _ => Err("invalid seat"),
+        })?;
+        Ok(Ferry { map })
+    }
+
+    /// Let people move until nobody does any more. Empty seats are taken
+    /// when none of the seats around them is, and people leave when at least
+    /// `crowded` of those are occupied.
+    pub fn settle(&mut self, neighbourhood: &Neighbourhood<2>, crowded: usize) {
+        let survival: Vec<usize> = (0..crowded).collect();
+        let mut automaton = Dense::from_grid(
+            &self.map,
+            |tile| match tile {
+                TileState::Floor => None,
+                TileState::Empty => Some(false),
+                TileState::Occupied => Some(true),
+            },
+            neighbourhood,
+            Rule::new(&[0], &survival),
+        );
+        automaton.run_until_stable();
+
+        self.map = Grid::from_fn(self.map.width(), self.map.height(), |x, y| {
+            match automaton.get([x as isize, y as isize]) {
+                None => TileState::Floor,
+                Some(false) => TileState::Empty,
+                Some(true) => TileState::Occupied,
+            }
+        });
+    }
+
+    pub fn count_occupied_seats(&self) -> usize {
+        self.map.iter().filter(|tile| tile == &&TileState::Occupied).count()
+    }
+}
+
+impl fmt::Display for Ferry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.map)
+    }
+}
+
+pub fn star_one(ferry: &mut Ferry) -> usize {
+    ferry.settle(&Neighbourhood::Moore, 4);
+    ferry.count_occupied_seats()
+}
+
+pub fn star_two(ferry: &mut Ferry) -> usize {
+    // People look past the floor to the first seat in every direction
+    ferry.settle(&Neighbourhood::LineOfSight, 5);
+    ferry.count_occupied_seats()
+}
+
+/// A seat layout of `size` by `size` positions, about three in four of them
+/// seats. Where everybody ends up is only known by letting them move.
+pub fn generate(rng: &mut Random, size: usize) -> Generated {
+    let size = size.max(1);
+    let map = Grid::from_fn(size, size, |_, _| {
+        if rng.gen_bool(0.75) {
+            TileState::Empty
+        } else {
+            TileState::Floor
+        }
+    });
+
+    Generated {
+        input: map.to_string().trim_end().to_string(),
+        answer1: None,
+        answer2: None,
+    }
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    type Puzzle = Ferry;
+
+    fn parse_lines(lines: impl Iterator<Item = String>) -> Result<Self::Puzzle, ParseError> {
+        Ferry::from_layout(lines)
+    }
+
+    fn part1(ferry: &Self::Puzzle) -> Option<String> {
+        Some(star_one(&mut ferry.clone()).to_string())
+    }
+
+    fn part2(ferry: &Self::Puzzle) -> Option<String> {
+        Some(star_two(&mut ferry.clone()).to_string())
+    }
+
+    fn generate(rng: &mut Random, size: usize) -> Option<Generated> {
+        Some(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    static TEST_DATA: &str = "L.LL.LL.LL
+LLLLLLL.LL
+L.L.L..L..
+LLLL.LL.LL
+L.LL.LL.LL
+L.LLLLL.LL
+..L.L.....
+LLLLLLLLLL
+L.LLLLLL.L
+L.LLLLL.LL";
+
+    #[test]
+    fn test_star_one() {
+        let mut ferry = super::Ferry::from_layout(TEST_DATA.lines()).expect("Invalid test data");
+
+        let ans = super::star_one(&mut ferry);
+        assert_eq!(ans, 37);
+    }
+
+    #[test]
+    fn test_star_two() {
+        let mut ferry = super::Ferry::from_layout(TEST_DATA.lines()).expect("Invalid test data");
+
+        let ans = super::star_two(&mut ferry);
+        assert_eq!(ans, 26);
+    }
+
+    #[test]
+    fn test_generate() {
+        let generated = crate::generate::check::<super::Day11>(0, 20);
+        assert_eq!(generated.input.lines().count(), 20);
+    }
+}
@@ -0,0 +1,298 @@
+use std::convert::TryFrom;
+use std::convert::TryInto;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::error::ParseError;
+use crate::generate::{Generated, Random};
+use crate::solution::Solution;
+
+#[derive(Debug)]
+pub enum Operation {
+    North(isize),
+    South(isize),
+    East(isize),
+    West(isize),
+    Left(isize),
+    Right(isize),
+    Forward(isize),
+}
+
+#[derive(Debug, Copy, Clone)]
+pub enum Direction {
+    // Confirmed that turns always are 90 degrees based on input data
+    North = 0,
+    East = 90,
+    South = 180,
+    West = 270,
+}
+
+impl TryFrom<isize> for Direction {
+    type Error = ();
+
+    fn try_from(v: isize) -> Result<Self, Self::Error> {
+        match v {
+            x if x == Direction::North as isize => Ok(Direction::North),
+            x if x == Direction::East as isize => Ok(Direction::East),
+            x if x == Direction::South as isize => Ok(Direction::South),
+            x if x == Direction::West as isize => Ok(Direction::West),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Ferry {
+    pub cur_dir: Direction,
+    pub cur_x: isize,
+    pub cur_y: isize,
+    pub waypoint_x: isize,
+    pub waypoint_y: isize,
+}
+
+impl Default for Ferry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Ferry {
+    pub fn new() -> Self {
+        Ferry {
+            cur_dir: Direction::East,
+            cur_x: 0,
+            cur_y: 0,
+            waypoint_x: 10,
+            waypoint_y: -1,
+        }
+    }
+
+    pub fn run_deduced_operations(&mut self, operations: &[Operation]) {
+        for oper in operations {
+            match oper {
+                Operation::North(operand) => self.cur_y -= operand,
+                Operation::South(operand) => self.cur_y += operand,
+                Operation::East(operand) => self.cur_x += operand,
+                Operation::West(operand) => self.cur_x -= operand,
+                Operation::Left(operand) => self.cur_dir = (self.cur_dir as isize - operand).rem_euclid(360).try_into().unwrap(),
+                Operation::Right(operand) => self.cur_dir = (self.cur_dir as isize + operand).rem_euclid(360).try_into().unwrap(),
+                Operation::Forward(operand) => match self.cur_dir {
+                    Direction::North => self.cur_y -= operand,
+                    Direction::South => self.cur_y += operand,
+                    Direction::East => self.cur_x += operand,
+                    Direction::West => self.cur_x -= operand,
+                }
+            }
+        }
+    }
+
+    pub fn run_real_operations(&mut self, operations: &[Operation]) {
+        for oper in operations {
+            match oper {
+                Operation::North(operand) => self.waypoint_y -= operand,
+                Operation::South(operand) => self.waypoint_y += operand,
+                Operation::East(operand) => self.waypoint_x += operand,
+                Operation::West(operand) => self.waypoint_x -= operand,
+                Operation::Left(operand) => match operand {
+                    270 => {
+                        let tmp = self.waypoint_x;
+                        self.waypoint_x = -self.waypoint_y;
+                        self.waypoint_y = tmp;
+                    },
+                    180 => {
+                        self.waypoint_x = -self.waypoint_x;
+                        self.waypoint_y = -self.waypoint_y;
+                    },
+                    90 => {
+                        let tmp = self.waypoint_x;
+                        self.waypoint_x = self.waypoint_y;
+                        self.waypoint_y = -tmp;
+                    },
+                    _ => unreachable!(),
+                },
+                Operation::Right(operand) => match operand {
+                    90 => {
+                        let tmp = self.waypoint_x;
+                        self.waypoint_x = -self.waypoint_y;
+                        self.waypoint_y = tmp;
+                    },
+                    180 => {
+                        self.waypoint_x = -self.waypoint_x;
+                        self.waypoint_y = -self.waypoint_y;
+                    },
+                    270 => {
+                        let tmp = self.waypoint_x;
+                        self.waypoint_x = self.waypoint_y;
+                        self.waypoint_y = -tmp;
+                    },
+                    _ => unreachable!(),
+                },
+                Operation::Forward(operand) => {
+                    self.cur_x += operand * self.waypoint_x;
+                    self.cur_y += operand * self.waypoint_y;
+                },
+            }
+        }
+    }
+
+    pub fn manhattan_distance(&self) -> usize {
+        TryInto::<usize>::try_into(self.cur_x.abs()).unwrap() + TryInto::<usize>::try_into(self.cur_y.abs()).unwrap()    
+    }
+}
+
+pub fn operation_from_string(line: &str) -> Result<Operation, ParseError> {
+    let invalid = |reason| ParseError::new(Day12::DAY, line, reason);
+
+    let mut chars = line.chars();
+    let action = chars.next().ok_or_else(|| invalid("missing action"))?;
+    let operand = chars
+        .as_str()
+        .parse::<isize>()
+        .map_err(|_| invalid("invalid operand").at_column(2))?;
+    if (action == 'L' || action == 'R') && operand % 90 != 0 {
+        return Err(invalid("turns should be a multiple of 90 degrees").at_column(2));
+    }
+    match action {
+        'N' => Ok(Operation::North(operand)),
+        'S' => Ok(Operation::South(operand)),
+        'E' => Ok(Operation::East(operand)),
+        'W' => Ok(Operation::West(operand)),
+        'L' => Ok(Operation::Left(operand)),
+        'R' => Ok(Operation::Right(operand)),
+        'F' => Ok(Operation::Forward(operand)),
+        _ => Err(invalid("unknown action").at_column(1)),
+    }
+}
+
+pub fn star_one(operations: &[Operation]) -> usize {
+    let mut ferry = Ferry::new();
+    ferry.run_deduced_operations(operations);
+    ferry.manhattan_distance()
+}
+
+pub fn star_two(operations: &[Operation]) -> usize {
+    let mut ferry = Ferry::new();
+    ferry.run_real_operations(operations);
+    ferry.manhattan_distance()
+}
+
+/// `size` random navigation instructions, with the ferry followed along the
+/// way both as the ship itself and as the waypoint.
+pub fn generate(rng: &mut Random, size: usize) -> Generated {
+    // East and south are positive, turning right is clockwise
+    let rotate = |(x, y): (isize, isize), degrees: isize| -> (isize, isize) {
+        (0..degrees.rem_euclid(360) / 90).fold((x, y), |(x, y), _| (-y, x))
+    };
+    let mut ship = (0, 0);
+    let mut heading = (1, 0);
+    let mut ship_two = (0, 0);
+    let mut waypoint = (10, -1);
+
+    let mut lines = vec![];
+    for _ in 0..size {
+        let action = *b"NSEWLRF".choose(rng).unwrap() as char;
+        let value: isize = match action {
+            'L' | 'R' => 90 * rng.gen_range(1..=3),
+            _ => rng.gen_range(1..=100),
+        };
+        lines.push(format!("{}{}", action, value));
+
+        let direction = match action {
+            'N' => Some((0, -1)),
+            'S' => Some((0, 1)),
+            'E' => Some((1, 0)),
+            'W' => Some((-1, 0)),
+            _ => None,
+        };
+        match (action, direction) {
+            (_, Some((dx, dy))) => {
+                ship = (ship.0 + dx * value, ship.1 + dy * value);
+                waypoint = (waypoint.0 + dx * value, waypoint.1 + dy * value);
+            }
+            ('L', _) => {
+                heading = rotate(heading, -value);
+                waypoint = rotate(waypoint, -value);
+            }
+            ('R', _) => {
+                heading = rotate(heading, value);
+                waypoint = rotate(waypoint, value);
+            }
+            _ => {
+                ship = (ship.0 + heading.0 * value, ship.1 + heading.1 * value);
+                ship_two = (ship_two.0 + waypoint.0 * value, ship_two.1 + waypoint.1 * value);
+            }
+        }
+    }
+
+    Generated {
+        input: lines.join("\n"),
+        answer1: Some((ship.0.abs() + ship.1.abs()).to_string()),
+        answer2: Some((ship_two.0.abs() + ship_two.1.abs()).to_string()),
+    }
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    type Puzzle = Vec<Operation>;
+
+    fn parse_lines(lines: impl Iterator<Item = String>) -> Result<Self::Puzzle, ParseError> {
+        lines
+            .enumerate()
+            .map(|(i, x)| operation_from_string(&x).map_err(|e| e.at_line(i + 1)))
+            .collect()
+    }
+
+    fn part1(operations: &Self::Puzzle) -> Option<String> {
+        Some(star_one(operations).to_string())
+    }
+
+    fn part2(operations: &Self::Puzzle) -> Option<String> {
+        Some(star_two(operations).to_string())
+    }
+
+    fn generate(rng: &mut Random, size: usize) -> Option<Generated> {
+        Some(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    static TEST_DATA: &str = "F10
+N3
+F7
+R90
+F11";
+
+    #[test]
+    fn test_star_one() {
+        let operations: Vec<super::Operation> = TEST_DATA
+            .lines()
+            .map(String::from)
+            .map(|x| super::operation_from_string(&x).expect("Invalid operation in input file"))
+            .collect();
+
+        let ans = super::star_one(&operations);
+        assert_eq!(ans, 25);
+    }
+
+    #[test]
+    fn test_star_two() {
+        let operations: Vec<super::Operation> = TEST_DATA
+            .lines()
+            .map(String::from)
+            .map(|x| super::operation_from_string(&x).expect("Invalid operation in input file"))
+            .collect();
+
+        let ans = super::star_two(&operations);
+        assert_eq!(ans, 286);
+    }
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..5 {
+            crate::generate::check::<super::Day12>(seed, 500);
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use std::vec::Vec;
+use log::debug;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::error::ParseError;
+use crate::generate::{Generated, Random};
+use crate::int::{self, Int, Overflow};
+use crate::solution::{Solution, Variant};
+
+pub fn star_one(earliest: isize, buses: &[isize]) -> isize {
+    let (bus, mins) = buses.iter().map(|x| (x, -(earliest % x) + x)).min_by(|x, y| x.1.cmp(&y.1)).unwrap();
+    bus * mins
+}
+
+pub fn star_two_peeked_solution(buses: &[(usize, isize)]) -> Result<Int, Overflow> {
+    // I cheated and peeked, I did not see the fact that we need to increment
+    // by a multiple of the previous bus nr in order to hold the invariant so far.
+    let mut time = Int::from(0);
+    let mut inc = Int::from(1);
+    for bus in buses {
+        let (offset, bus) = (int::from(bus.0)?, int::from(bus.1)?);
+        while !int::is_multiple_of(&int::add(&time, &offset)?, &bus) {
+            time = int::add(&time, &inc)?;
+        }
+
+        inc = int::mul(&inc, &bus)?;
+    }
+
+    Ok(time)
+}
+
+pub fn star_two(buses: &[(usize, isize)]) -> isize {
+    // This version does not complete in reasonable time but was the best I could think of
+    let max_bus = buses.iter().max_by(|(_, busa), (_, busb)| busa.cmp(busb)).unwrap();
+    let mut check: isize = -(max_bus.0 as isize) + max_bus.1;
+    let mut nr_checks: usize = 0;
+    loop {
+        let result = buses.iter().fold(true, |s, (idx, b)| {
+            s & (check % b == (-(*idx as isize) + b) % b)
+        });
+        if result {
+            return check;
+        }
+        // Check faster by ensuring we increment by the maximum possible bus nr
+        check += max_bus.1;
+        nr_checks += 1;
+        if nr_checks.is_multiple_of(100_000_000) {
+            debug!("Still searching after {} checks, at {}", nr_checks, check);
+        }
+    }
+}
+
+pub fn parse_buses(line: &str) -> Result<Vec<isize>, ParseError> {
+    Ok(parse_buses_with_offset(line)?.into_iter().map(|(_, bus)| bus).collect())
+}
+
+pub fn parse_buses_with_offset(line: &str) -> Result<Vec<(usize, isize)>, ParseError> {
+    let mut buses = vec![];
+    let mut column = 1;
+    for (idx, x) in line.split(',').enumerate() {
+        if x != "x" {
+            match x.parse::<isize>() {
+                Ok(bus) if bus > 0 => buses.push((idx, bus)),
+                _ => return Err(ParseError::new(Day13::DAY, x, "invalid bus ID").at_column(column)),
+            }
+        }
+        column += x.len() + 1;
+    }
+
+    Ok(buses)
+}
+
+/// Both ways of reading the notes: the bus IDs that are in service, and the
+/// bus IDs with their offset in the schedule.
+#[derive(Debug)]
+pub struct Notes {
+    pub earliest: isize,
+    pub buses: Vec<isize>,
+    pub buses_with_offset: Vec<(usize, isize)>,
+}
+
+static PRIMES: [isize; 30] = [
+    7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97, 101, 103, 107, 109, 113,
+    127, 131, 137,
+];
+
+/// Notes with up to `size` buses in service. The bus IDs are distinct primes
+/// so the timestamp of part two is unique below their product, and it is
+/// picked first with the offsets worked out from it.
+pub fn generate(rng: &mut Random, size: usize) -> Generated {
+    let mut primes = PRIMES.to_vec();
+    primes.shuffle(rng);
+    let mut buses = vec![];
+    let mut product: isize = 1;
+    for &prime in primes.iter().take(size.max(2)) {
+        if product * prime > 10_000_000_000_000_000 {
+            break;
+        }
+        product *= prime;
+        buses.push(prime);
+    }
+
+    // The first bus leaves at the timestamp itself
+    let timestamp = buses[0] * rng.gen_range(1..product / buses[0]);
+    let mut offsets: Vec<usize> = vec![0];
+    for &bus in buses.iter().skip(1) {
+        let mut offset = (-timestamp).rem_euclid(bus) as usize;
+        offset += bus as usize * rng.gen_range(0..3);
+        while offsets.contains(&offset) {
+            offset += bus as usize;
+        }
+        offsets.push(offset);
+    }
+    let mut schedule = vec![String::from("x"); offsets.iter().max().unwrap() + 1];
+    for (&bus, &offset) in buses.iter().zip(offsets.iter()) {
+        schedule[offset] = bus.to_string();
+    }
+
+    // Skip the moments where a bus leaves right away or two buses tie
+    let (earliest, bus, wait) = loop {
+        let earliest: isize = rng.gen_range(1000..1_000_000);
+        let mut waits: Vec<(isize, isize)> = buses.iter().map(|&bus| ((bus - earliest % bus) % bus, bus)).collect();
+        waits.sort_unstable();
+        if waits[0].0 > 0 && (waits.len() < 2 || waits[0].0 < waits[1].0) {
+            break (earliest, waits[0].1, waits[0].0);
+        }
+    };
+
+    Generated {
+        input: format!("{}\n{}", earliest, schedule.join(",")),
+        answer1: Some((bus * wait).to_string()),
+        answer2: Some(timestamp.to_string()),
+    }
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+    type Puzzle = Notes;
+
+    fn parse_lines(mut lines: impl Iterator<Item = String>) -> Result<Self::Puzzle, ParseError> {
+        let first = lines
+            .next()
+            .ok_or_else(|| ParseError::new(Self::DAY, "", "missing earliest departure time"))?;
+        let earliest = first
+            .parse::<isize>()
+            .map_err(|_| ParseError::new(Self::DAY, &first, "invalid earliest departure time").at_line(1))?;
+        let schedule = lines
+            .next()
+            .ok_or_else(|| ParseError::new(Self::DAY, "", "missing bus schedule"))?;
+
+        let buses = parse_buses(&schedule).map_err(|e| e.at_line(2))?;
+        if buses.is_empty() {
+            return Err(ParseError::new(Self::DAY, &schedule, "no buses in service").at_line(2));
+        }
+
+        Ok(Notes {
+            earliest,
+            buses,
+            buses_with_offset: parse_buses_with_offset(&schedule).map_err(|e| e.at_line(2))?,
+        })
+    }
+
+    fn part1(notes: &Self::Puzzle) -> Option<String> {
+        Some(star_one(notes.earliest, &notes.buses).to_string())
+    }
+
+    fn part2(notes: &Self::Puzzle) -> Option<String> {
+        int::answer(Self::DAY, star_two_peeked_solution(&notes.buses_with_offset))
+    }
+
+    fn variants(part: u8) -> Vec<Variant<Self::Puzzle>> {
+        let variants: Vec<Variant<Self::Puzzle>> = match part {
+            1 => vec![("default", Self::part1)],
+            _ => vec![
+                ("sieve", Self::part2),
+                // Does not finish on real inputs
+                ("brute", |notes| Some(star_two(&notes.buses_with_offset).to_string())),
+            ],
+        };
+        variants
+    }
+
+    fn generate(rng: &mut Random, size: usize) -> Option<Generated> {
+        Some(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    static TEST_DATA: &str = "939
+7,13,x,x,59,x,31,19";
+
+    #[test]
+    fn test_star_one() {
+        let mut lines = TEST_DATA.lines().map(String::from);
+        let earliest = lines.next().unwrap().parse::<isize>().expect("Invalid first line");
+        let buses = super::parse_buses(&lines.next().unwrap()).expect("Invalid test data");
+        let ans = super::star_one(earliest, &buses);
+        assert_eq!(ans, 59 * 5);
+    }
+
+    #[test]
+    fn test_star_two() {
+        let mut lines = TEST_DATA.lines().map(String::from);
+        lines.next().unwrap();
+        let buses = super::parse_buses_with_offset(&lines.next().unwrap()).expect("Invalid test data");
+        let ans = super::star_two_peeked_solution(&buses);
+        assert_eq!(ans, Ok(1068781.into()));
+    }
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..5 {
+            crate::generate::check::<super::Day13>(seed, 9);
+        }
+        crate::generate::check::<super::Day13>(0, 1);
+    }
+}
@@ -0,0 +1,262 @@
+use std::collections::HashMap;
+use std::vec::Vec;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::error::ParseError;
+use crate::generate::{Generated, Random};
+use crate::solution::Solution;
+
+#[derive(Debug)]
+pub enum Operation {
+    Mask(String),
+    MemSet(isize, isize),
+}
+
+pub fn parse_operation(line: &str) -> Result<Operation, ParseError> {
+    let invalid = |reason| ParseError::new(Day14::DAY, line, reason);
+
+    let mut split = line.split(" = ");
+    let val = split.next().unwrap_or_default();
+    let raw_operand = split.next().ok_or_else(|| invalid("missing ' = '"))?;
+    if val == "mask" {
+        if raw_operand.len() != 36 || !raw_operand.chars().all(|c| c == '0' || c == '1' || c == 'X') {
+            return Err(invalid("mask should be 36 characters of 0, 1 or X").at_column(8));
+        }
+        return Ok(Operation::Mask(String::from(raw_operand)));
+    } else if let Some(address) = val.strip_prefix("mem[") {
+        let end_index = address.find(']').ok_or_else(|| invalid("missing ']'"))?;
+        let index = address[..end_index]
+            .parse::<isize>()
+            .map_err(|_| invalid("invalid memory address").at_column(5))?;
+        let operand = raw_operand
+            .parse::<isize>()
+            .map_err(|_| invalid("invalid value").at_column(val.len() + 4))?;
+        return Ok(Operation::MemSet(index, operand));
+    }
+
+    Err(invalid("unknown operation").at_column(1))
+}
+
+pub fn star_one(operations: &[Operation]) -> isize {
+    let mut and_mask: isize = 0;
+    let mut or_mask: isize = 0;
+    let mut memory: HashMap<isize, isize> = HashMap::new();
+    for oper in operations {
+        match oper {
+            Operation::Mask(operand) => {
+                and_mask = isize::from_str_radix(&operand.replace("X", "1"), 2).unwrap();
+                or_mask = isize::from_str_radix(&operand.replace("X", "0"), 2).unwrap();
+            },
+            Operation::MemSet(index, operand) => {
+                *memory.entry(*index).or_insert(0) = (operand & and_mask) | or_mask;
+            }
+        }
+    }
+
+    memory.iter().fold(0, |s, (_, val)| s + val)
+}
+
+// Tuple (and_mask, or_mask)
+pub fn generate_masks(mask: &str) -> Vec<(isize, isize)> {
+    let mut result: Vec<(isize, isize)> = vec![(0, 0)];
+    for (idx, ch) in mask.chars().rev().enumerate() {
+        match ch {
+            '0' => {
+                for r in result.iter_mut() {
+                    r.0 |= 1 << idx;
+                }
+            },
+            '1' => {
+                for r in result.iter_mut() {
+                    r.0 |= 1 << idx;
+                    r.1 |= 1 << idx;
+                }
+            },
+            'X' => {
+                let mut to_add: Vec<(isize, isize)> = vec![];
+                for r in result.iter_mut() {
+                    // Fork into the two different options
+                    r.1 |= 1 << idx;
+
+                    let mut copy = *r;
+                    copy.0 |= 1 << idx;
+                    to_add.push(copy);
+                }
+
+                result.append(&mut to_add);
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    result
+}
+
+pub fn star_two(operations: &[Operation]) -> isize {
+    let mut masks: Vec<(isize, isize)> = vec![];
+    let mut memory: HashMap<isize, isize> = HashMap::new();
+    for oper in operations {
+        match oper {
+            Operation::Mask(operand) => {
+                masks = generate_masks(operand);
+            },
+            Operation::MemSet(index, operand) => {
+                for mask in &masks {
+                    *memory.entry((*index | mask.1) & mask.0).or_insert(0) = *operand;
+                }
+            }
+        }
+    }
+
+    memory.iter().fold(0, |s, (_, val)| s + val)
+}
+
+/// `size` masks, each followed by a few writes. Masks have at most 9 floating
+/// bits to keep part two small; both parts are worked out along the way.
+pub fn generate(rng: &mut Random, size: usize) -> Generated {
+    let mut lines = vec![];
+    let mut memory_one: HashMap<u64, u64> = HashMap::new();
+    let mut memory_two: HashMap<u64, u64> = HashMap::new();
+    for _ in 0..size.max(1) {
+        let mut mask: Vec<char> = (0..36).map(|_| *['0', '1'].choose(rng).unwrap()).collect();
+        let nr_floating = rng.gen_range(0..=9);
+        for bit in rand::seq::index::sample(rng, 36, nr_floating) {
+            mask[bit] = 'X';
+        }
+        lines.push(format!("mask = {}", mask.iter().collect::<String>()));
+
+        // Bit 35 comes first in the mask
+        let bits_of = |ch: char| mask.iter().fold(0u64, |s, &c| s << 1 | (c == ch) as u64);
+        let (ones, floating) = (bits_of('1'), bits_of('X'));
+        let floating_bits: Vec<u64> = (0..36).map(|i| 1 << i).filter(|b| floating & b != 0).collect();
+
+        for _ in 0..rng.gen_range(1..=4) {
+            let address: u64 = rng.gen_range(0..65536);
+            let value: u64 = rng.gen_range(0..1 << 36);
+            lines.push(format!("mem[{}] = {}", address, value));
+
+            memory_one.insert(address, value & floating | ones);
+            for choice in 0..1u64 << floating_bits.len() {
+                let mut target = (address | ones) & !floating;
+                for (i, bit) in floating_bits.iter().enumerate() {
+                    if choice >> i & 1 == 1 {
+                        target |= bit;
+                    }
+                }
+                memory_two.insert(target, value);
+            }
+        }
+    }
+
+    Generated {
+        input: lines.join("\n"),
+        answer1: Some(memory_one.values().sum::<u64>().to_string()),
+        answer2: Some(memory_two.values().sum::<u64>().to_string()),
+    }
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    type Puzzle = Vec<Operation>;
+
+    fn parse_lines(lines: impl Iterator<Item = String>) -> Result<Self::Puzzle, ParseError> {
+        lines
+            .enumerate()
+            .map(|(i, x)| parse_operation(&x).map_err(|e| e.at_line(i + 1)))
+            .collect()
+    }
+
+    fn part1(operations: &Self::Puzzle) -> Option<String> {
+        Some(star_one(operations).to_string())
+    }
+
+    fn part2(operations: &Self::Puzzle) -> Option<String> {
+        Some(star_two(operations).to_string())
+    }
+
+    fn generate(rng: &mut Random, size: usize) -> Option<Generated> {
+        Some(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use proptest::prelude::*;
+
+    /// Masks with few enough floating bits to list all their addresses.
+    fn mask() -> impl Strategy<Value = String> {
+        let bit = prop_oneof![4 => Just('0'), 4 => Just('1'), 1 => Just('X')];
+        prop::collection::vec(bit, 36)
+            .prop_filter("too many floating bits", |bits| bits.iter().filter(|&&b| b == 'X').count() <= 10)
+            .prop_map(|bits| bits.into_iter().collect())
+    }
+
+    static TEST_DATA_1: &str = "mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X
+mem[8] = 11
+mem[7] = 101
+mem[8] = 0";
+
+    #[test]
+    fn test_star_one() {
+        let operations: Vec<super::Operation> = TEST_DATA_1
+            .lines()
+            .map(String::from)
+            .map(|x| super::parse_operation(&x).expect("Invalid operation in input file"))
+            .collect();
+
+        let ans = super::star_one(&operations);
+        assert_eq!(ans, 165);
+    }
+
+    static TEST_DATA_2: &str = "mask = 000000000000000000000000000000X1001X
+mem[42] = 100
+mask = 00000000000000000000000000000000X0XX
+mem[26] = 1";
+
+    #[test]
+    fn test_star_two() {
+        let operations: Vec<super::Operation> = TEST_DATA_2
+            .lines()
+            .map(String::from)
+            .map(|x| super::parse_operation(&x).expect("Invalid operation in input file"))
+            .collect();
+
+        let ans = super::star_two(&operations);
+        assert_eq!(ans, 208);
+    }
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..5 {
+            crate::generate::check::<super::Day14>(seed, 50);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn prop_generate_masks(mask in mask(), address in 0..1isize << 36) {
+            let masks = super::generate_masks(&mask);
+            let nr_floating = mask.matches('X').count();
+            prop_assert_eq!(masks.len(), 1 << nr_floating);
+            prop_assert_eq!(masks.iter().collect::<HashSet<_>>().len(), masks.len());
+
+            // Every mask writes a different address, with the 0 and 1 bits
+            // applied as in the puzzle
+            let addresses: HashSet<isize> = masks.iter().map(|(and, or)| (address | or) & and).collect();
+            prop_assert_eq!(addresses.len(), masks.len());
+            for (i, ch) in mask.chars().rev().enumerate() {
+                let bits: HashSet<isize> = addresses.iter().map(|a| a >> i & 1).collect();
+                match ch {
+                    '0' => prop_assert_eq!(bits, [address >> i & 1].iter().copied().collect()),
+                    '1' => prop_assert_eq!(bits, [1].iter().copied().collect()),
+                    _ => prop_assert_eq!(bits.len(), 2),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use std::collections::hash_map::Entry::Occupied;
+use std::collections::hash_map::Entry::Vacant;
+use std::collections::HashMap;
+use rand::seq::SliceRandom;
+
+use crate::error::ParseError;
+use crate::generate::{Generated, Random};
+use crate::solution::Solution;
+
+pub fn star_one(starting: &[isize], until: isize) -> isize {
+    let mut memory: HashMap<isize, isize> = HashMap::new();
+    let mut time: isize = 1;
+    let mut s = starting.iter();
+    let mut to_say: isize = *s.next().unwrap();
+    let mut cur = to_say;
+
+    while time <= until {
+        cur = to_say;
+        time += 1;
+
+        match s.next() {
+            Some(val) => {
+                // Starting sequence, just say it next time
+                to_say = *val;
+            },
+            None => {
+                // Check memory of last spoken to determine next thing to say
+                to_say = match memory.entry(cur) {
+                    Vacant(_) => {
+                        // Not yet spoken, say 0 next
+                        0
+                    },
+                    Occupied(entry) => {
+                        time - entry.get()
+                    },
+                };
+            }
+        }
+
+        memory.insert(cur, time);
+    }
+
+    cur
+}
+
+/// `size` distinct starting numbers. The 2020th number is worked out here,
+/// the 30000000th only by playing the game in full.
+pub fn generate(rng: &mut Random, size: usize) -> Generated {
+    let size = size.clamp(1, 20);
+    let mut numbers: Vec<usize> = (0..20).collect();
+    numbers.shuffle(rng);
+    numbers.truncate(size);
+
+    // The turn each number was last spoken before the previous turn
+    let mut last_spoken = vec![None; 2020];
+    let mut spoken = numbers[0];
+    for turn in 1..2020 {
+        let next = if turn < numbers.len() {
+            numbers[turn]
+        } else {
+            last_spoken[spoken].map_or(0, |last| turn - last)
+        };
+        last_spoken[spoken] = Some(turn);
+        spoken = next;
+    }
+
+    let input: Vec<String> = numbers.iter().map(|x| x.to_string()).collect();
+    Generated {
+        input: input.join(","),
+        answer1: Some(spoken.to_string()),
+        answer2: None,
+    }
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+    type Puzzle = Vec<isize>;
+
+    fn parse_lines(mut lines: impl Iterator<Item = String>) -> Result<Self::Puzzle, ParseError> {
+        // The starting numbers are all on the first line
+        let line = lines.next().unwrap_or_default();
+        let mut starting = vec![];
+        let mut column = 1;
+        for x in line.trim().split(',') {
+            let number = x.parse::<isize>().map_err(|e| {
+                ParseError::new(Self::DAY, x, format!("invalid number ({})", e))
+                    .at_line(1)
+                    .at_column(column)
+            })?;
+            starting.push(number);
+            column += x.len() + 1;
+        }
+
+        Ok(starting)
+    }
+
+    fn part1(starting: &Self::Puzzle) -> Option<String> {
+        Some(star_one(starting, 2020).to_string())
+    }
+
+    fn part2(starting: &Self::Puzzle) -> Option<String> {
+        Some(star_one(starting, 30000000).to_string())
+    }
+
+    fn generate(rng: &mut Random, size: usize) -> Option<Generated> {
+        Some(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_star_one() {
+        let test_data: Vec<isize> = vec![0,3,6];
+        let ans = super::star_one(&test_data, 2020);
+        assert_eq!(ans, 436);
+    }
+
+    #[test]
+    fn test_star_two() {
+        let test_data: Vec<isize> = vec![0,3,6];
+        let ans = super::star_one(&test_data, 30000000);
+        assert_eq!(ans, 175594);
+    }
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..5 {
+            crate::generate::check::<super::Day15>(seed, 3);
+        }
+        let generated = crate::generate::check::<super::Day15>(0, 40);
+        assert_eq!(generated.input.split(',').count(), 20);
+    }
+}
@@ -0,0 +1,424 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::ops::Range;
+use std::vec::Vec;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::error::ParseError;
+use crate::generate::{self, Generated, Random};
+use crate::solution::Solution;
+
+#[derive(Debug, Clone)]
+pub struct Rule {
+    pub name: String,
+    pub field_index: Option<usize>,
+    pub range_1: Range<isize>,
+    pub range_2: Range<isize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Ticket {
+    pub values: Vec<isize>,
+}
+
+#[derive(Debug)]
+pub struct TrainTickets {
+    pub rules: Vec<Rule>,
+    pub my_ticket: Ticket,
+    pub nearby_tickets: Vec<Ticket>,
+}
+
+#[derive(Debug)]
+enum ParseState {
+    Rules,
+    MyTicket,
+    NearbyTickets,
+}
+
+fn parse_range(range: &str) -> Result<Range<isize>, ParseError> {
+    let invalid = |reason| ParseError::new(Day16::DAY, range.trim(), reason);
+
+    let mut split = range.trim().split('-');
+    let start: isize = match split.next() {
+        Some(val) => match val.parse::<isize>() {
+            Ok(v) => v,
+            Err(_) => return Err(invalid("invalid start of range")),
+        },
+        None => return Err(invalid("missing start of range")),
+    };
+
+    let end: isize = match split.next() {
+        Some(val) => {
+            match val.parse::<isize>() {
+                // Rust ranges are exclusive
+                Ok(v) => v + 1,
+                Err(_) => return Err(invalid("invalid end of range")),
+            }
+        }
+        None => return Err(invalid("missing '-' in range")),
+    };
+
+    if split.next().is_some() {
+        return Err(invalid("more than one '-' in range"));
+    }
+
+    Ok(start..end)
+}
+
+fn parse_rule(line: &str) -> Result<Rule, ParseError> {
+    let invalid = |reason| ParseError::new(Day16::DAY, line, reason);
+
+    let mut split = line.split(':');
+    let name = match split.next() {
+        Some(val) => val,
+        None => return Err(invalid("missing rule name")),
+    };
+    let ranges = match split.next() {
+        Some(val) => val.trim(),
+        None => return Err(invalid("missing ':' after rule name")),
+    };
+    if split.next().is_some() {
+        return Err(invalid("more than one ':' in rule"));
+    }
+    let mut split = ranges.split(" or ");
+    let range_1 = match split.next() {
+        Some(val) => parse_range(val)?,
+        None => return Err(invalid("missing first range")),
+    };
+    let range_2 = match split.next() {
+        Some(val) => parse_range(val)?,
+        None => return Err(invalid("missing ' or ' between ranges")),
+    };
+    if split.next().is_some() {
+        return Err(invalid("more than two ranges"));
+    }
+
+    Ok(Rule {
+        name: String::from(name),
+        field_index: None,
+        range_1,
+        range_2,
+    })
+}
+
+fn parse_ticket(line: &str, nr_fields: usize) -> Result<Ticket, ParseError> {
+    let mut ticket = Ticket { values: vec![] };
+    for val in line.split(',') {
+        match val.parse::<isize>() {
+            Ok(v) => ticket.values.push(v),
+            Err(_) => return Err(ParseError::new(Day16::DAY, val, "invalid ticket value")),
+        }
+    }
+    if ticket.values.len() != nr_fields {
+        return Err(ParseError::new(
+            Day16::DAY,
+            line,
+            format!("ticket should have {} values, one for every rule", nr_fields),
+        ));
+    }
+
+    Ok(ticket)
+}
+
+pub fn parse_lines<T>(lines: &mut T) -> Result<TrainTickets, ParseError>
+where
+    T: Iterator<Item = String>,
+{
+    let mut train_tickets = TrainTickets {
+        rules: vec![],
+        my_ticket: Ticket { values: vec![] },
+        nearby_tickets: vec![],
+    };
+    let mut state = ParseState::Rules;
+    for (i, line) in lines.enumerate() {
+        let at_line = |e: ParseError| e.at_line(i + 1);
+        match state {
+            ParseState::Rules => {
+                if line.is_empty() {
+                    state = ParseState::MyTicket;
+                    continue;
+                }
+
+                let rule = parse_rule(&line).map_err(at_line)?;
+                train_tickets.rules.push(rule);
+            }
+            ParseState::MyTicket => {
+                if line == "your ticket:" {
+                    // Skip the header
+                    continue;
+                } else if line.is_empty() {
+                    state = ParseState::NearbyTickets;
+                    continue;
+                }
+
+                train_tickets.my_ticket = parse_ticket(&line, train_tickets.rules.len()).map_err(at_line)?;
+            }
+            ParseState::NearbyTickets => {
+                if line == "nearby tickets:" {
+                    // Skip the header
+                    continue;
+                }
+
+                let ticket = parse_ticket(&line, train_tickets.rules.len()).map_err(at_line)?;
+                train_tickets.nearby_tickets.push(ticket);
+            }
+        }
+    }
+
+    Ok(train_tickets)
+}
+
+pub fn star_one(train_tickets: &TrainTickets) -> isize {
+    let mut invalid_values: Vec<isize> = vec![];
+    for ticket in train_tickets.nearby_tickets.iter() {
+        for value in ticket.values.iter() {
+            let valid = train_tickets.rules.iter().fold(false, |s, x| {
+                s || x.range_1.contains(value) || x.range_2.contains(value)
+            });
+            if !valid {
+                invalid_values.push(*value);
+            }
+        }
+    }
+
+    invalid_values.iter().sum()
+}
+
+pub fn star_two(train_tickets: &TrainTickets, prefix: &str) -> isize {
+    // Assert that we have the same amount of rules as we have fields
+    assert_eq!(
+        train_tickets.rules.len(),
+        train_tickets.my_ticket.values.len()
+    );
+
+    let mut valid_tickets: Vec<Ticket> = vec![];
+    for ticket in train_tickets.nearby_tickets.iter() {
+        let valid = ticket.values.iter().all(|x| {
+            // All values should test true when it matches at least one rule
+            train_tickets.rules.iter().any(|x2| {
+                x2.range_1.contains(x) || x2.range_2.contains(x)
+            })
+        });
+        if valid {
+            valid_tickets.push(ticket.clone());
+        }
+    }
+
+    let mut possible_fields: HashMap<usize, Vec<usize>> = HashMap::new();
+
+    for (idx, rule) in train_tickets.rules.iter().enumerate() {
+        for field_index in 0..train_tickets.my_ticket.values.len() {
+            let mut valid = true;
+            for ticket in valid_tickets.iter() {
+                if !rule.range_1.contains(&ticket.values[field_index])
+                    && !rule.range_2.contains(&ticket.values[field_index])
+                {
+                    valid = false;
+                    break;
+                }
+            }
+
+            if valid {
+                // Rule is valid for this field index, store that fact and break to next rule
+                possible_fields.entry(field_index).or_default().push(idx);
+            }
+        }
+    }
+
+    let mut rule_mapping: HashMap<usize, usize> = HashMap::new();
+    for _ in 0..train_tickets.my_ticket.values.len() {
+        let candidate: Vec<(&usize, &Vec<usize>)> = possible_fields.iter().filter(|(_, v)| v.len() == 1).collect();
+        assert_eq!(candidate.len(), 1);
+        let field_index = candidate[0].0;
+        let rule_index = candidate[0].1[0];
+        *rule_mapping.entry(rule_index).or_default() = *field_index;
+        for (_, v) in possible_fields.iter_mut() {
+            v.retain(|&x| x != rule_index);
+        }
+    }
+
+    train_tickets.rules
+        .iter()
+        .enumerate()
+        .fold(1, |s, (idx, x)| if x.name.starts_with(prefix) {
+            s * train_tickets.my_ticket.values[rule_mapping[&idx]]
+        } else {
+            s
+        })
+}
+
+/// Notes with `size` fields (at most 20) that have a single assignment of
+/// fields to positions. All rules share the upper range, and the lower range
+/// of every next rule starts higher, so the field at position `j` of a ticket
+/// fits the first `j + 1` rules: assigning the fields one by one always
+/// leaves exactly one that fits a single position.
+pub fn generate(rng: &mut Random, size: usize) -> Generated {
+    let nr_fields = size.clamp(2, 20);
+    let low = |j: usize| 25 + 15 * j as isize;
+    let gap_start = low(nr_fields) + rng.gen_range(10..100);
+    let gap_end = gap_start + rng.gen_range(5..50);
+    let top = gap_end + rng.gen_range(100..500);
+    let allowed = |j: usize, rng: &mut Random| {
+        if rng.gen_bool(0.5) {
+            rng.gen_range(low(j)..gap_start)
+        } else {
+            rng.gen_range(gap_end..=top)
+        }
+    };
+
+    let nr_departures = (nr_fields / 2).clamp(1, 6);
+    let mut names = HashSet::new();
+    while names.len() < nr_fields {
+        names.insert(generate::word(rng, 3, 8));
+    }
+    let mut names: Vec<String> = names.into_iter().collect();
+    names.sort();
+    names.shuffle(rng);
+    for name in names.iter_mut().take(nr_departures) {
+        *name = format!("departure {}", name);
+    }
+    let mut rules: Vec<String> = (0..nr_fields)
+        .map(|j| format!("{}: {}-{} or {}-{}", names[j], low(j), gap_start - 1, gap_end, top))
+        .collect();
+    rules.shuffle(rng);
+
+    // Where the field of every rule is on a ticket
+    let mut columns: Vec<usize> = (0..nr_fields).collect();
+    columns.shuffle(rng);
+    let ticket = |rng: &mut Random| {
+        let mut values = vec![0; nr_fields];
+        for (j, &column) in columns.iter().enumerate() {
+            values[column] = allowed(j, rng);
+        }
+        values
+    };
+
+    let my_ticket = ticket(rng);
+    let mut nearby: Vec<Vec<isize>> = (0..3 * nr_fields + 5).map(|_| ticket(rng)).collect();
+    // A value that fits the rules up to `j` only, so the field can't be
+    // placed any further along
+    for (j, &column) in columns.iter().enumerate() {
+        let witness = rng.gen_range(low(j)..low(j + 1));
+        let nr_nearby = nearby.len();
+        nearby[rng.gen_range(0..nr_nearby)][column] = witness;
+    }
+
+    let mut error_rate = 0;
+    for _ in 0..nr_fields + 2 {
+        let value = match rng.gen_range(0..3) {
+            0 => rng.gen_range(0..low(0)),
+            1 => rng.gen_range(gap_start..gap_end),
+            _ => rng.gen_range(top + 1..1000),
+        };
+        error_rate += value;
+        let mut invalid = ticket(rng);
+        invalid[rng.gen_range(0..nr_fields)] = value;
+        nearby.push(invalid);
+    }
+    nearby.shuffle(rng);
+
+    let departure: isize = (0..nr_departures).map(|j| my_ticket[columns[j]]).product();
+    let line = |values: &[isize]| values.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(",");
+    let nearby: Vec<String> = nearby.iter().map(|values| line(values)).collect();
+    Generated {
+        input: format!(
+            "{}\n\nyour ticket:\n{}\n\nnearby tickets:\n{}",
+            rules.join("\n"),
+            line(&my_ticket),
+            nearby.join("\n")
+        ),
+        answer1: Some(error_rate.to_string()),
+        answer2: Some(departure.to_string()),
+    }
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+    type Puzzle = TrainTickets;
+
+    fn parse_lines(mut lines: impl Iterator<Item = String>) -> Result<Self::Puzzle, ParseError> {
+        parse_lines(&mut lines)
+    }
+
+    fn part1(train_tickets: &Self::Puzzle) -> Option<String> {
+        Some(star_one(train_tickets).to_string())
+    }
+
+    fn part2(train_tickets: &Self::Puzzle) -> Option<String> {
+        Some(star_two(train_tickets, "departure").to_string())
+    }
+
+    fn generate(rng: &mut Random, size: usize) -> Option<Generated> {
+        Some(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    static TEST_DATA_1: &str = "class: 1-3 or 5-7
+row: 6-11 or 33-44
+seat: 13-40 or 45-50
+
+your ticket:
+7,1,14
+
+nearby tickets:
+7,3,47
+40,4,50
+55,2,20
+38,6,12";
+
+    static TEST_DATA_2: &str = "class: 0-1 or 4-19
+row: 0-5 or 8-19
+seat: 0-13 or 16-19
+
+your ticket:
+11,12,13
+
+nearby tickets:
+3,9,18
+15,1,5
+5,14,9";
+
+    #[test]
+    fn test_star_one() {
+        let mut lines = TEST_DATA_1.lines().map(String::from);
+        let train_tickets = super::parse_lines(&mut lines).expect("Invalid input in file");
+
+        assert_eq!(train_tickets.rules[0].name, "class");
+        assert_eq!(train_tickets.rules[0].range_1, 1..4);
+        assert_eq!(train_tickets.rules[0].range_2, 5..8);
+
+        assert_eq!(train_tickets.nearby_tickets[0].values.len(), 3);
+        assert_eq!(train_tickets.nearby_tickets[0].values[0], 7);
+        assert_eq!(train_tickets.nearby_tickets[0].values[1], 3);
+        assert_eq!(train_tickets.nearby_tickets[0].values[2], 47);
+
+        let ans = super::star_one(&train_tickets);
+        assert_eq!(ans, 71);
+    }
+
+    #[test]
+    fn test_star_two() {
+        let mut lines = TEST_DATA_2.lines().map(String::from);
+        let train_tickets = super::parse_lines(&mut lines).expect("Invalid input in file");
+
+        assert_eq!(train_tickets.rules[0].name, "class");
+        assert_eq!(train_tickets.rules[0].range_1, 0..2);
+        assert_eq!(train_tickets.rules[0].range_2, 4..20);
+
+        let ans = super::star_two(&train_tickets, "");
+        assert_eq!(ans, 1716);
+    }
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..5 {
+            crate::generate::check::<super::Day16>(seed, 20);
+        }
+        crate::generate::check::<super::Day16>(0, 1);
+    }
+}
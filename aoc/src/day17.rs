@@ -0,0 +1,193 @@
+use rand::Rng;
+
+use crate::automaton::{Neighbourhood, Rule, Sparse};
+use crate::error::ParseError;
+use crate::generate::{Generated, Random};
+use crate::grid::Grid;
+use crate::solution::Solution;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum CubeState {
+    Inactive = 0,
+    Active = 1,
+}
+
+/// The input is a 2D slice of the pocket dimension.
+pub fn parse_input<L: AsRef<str>>(lines: impl Iterator<Item = L>) -> Result<Grid<CubeState>, ParseError> {
+    let slice = Grid::from_lines(Day17::DAY, lines, |ch| match ch {
+        '.' => Ok(CubeState::Inactive),
+        '#' => Ok(CubeState::Active),
+        _ => Err("invalid cube state"),
+    })?;
+    if !slice.iter().any(|&cube| cube == CubeState::Active) {
+        return Err(ParseError::new(Day17::DAY, "", "no cubes in initial state"));
+    }
+
+    Ok(slice)
+}
+
+/// Put the initial slice in a pocket dimension with `N` dimensions, at 0 in
+/// all other dimensions.
+pub fn pocket_dimension<const N: usize>(slice: &Grid<CubeState>) -> Sparse<N> {
+    assert!(N >= 2, "The initial state does not fit in {} dimensions", N);
+    let active = slice
+        .positions()
+        .filter(|&pos| slice[pos] == CubeState::Active)
+        .map(|(x, y)| {
+            let mut cube = [0; N];
+            cube[0] = x as isize;
+            cube[1] = y as isize;
+            cube
+        });
+    Sparse::new(active, &Neighbourhood::Moore, Rule::new(&[3], &[2, 3]))
+}
+
+/// Run the boot process in `N` dimensions, giving the number of active cubes
+/// afterwards.
+pub fn boot<const N: usize>(slice: &Grid<CubeState>, cycles: usize) -> usize {
+    let mut dimension = pocket_dimension::<N>(slice);
+    dimension.run(cycles);
+
+    dimension.nr_alive()
+}
+
+pub fn star_one(slice: &Grid<CubeState>) -> usize {
+    boot::<3>(slice, 6)
+}
+
+pub fn star_two(slice: &Grid<CubeState>) -> usize {
+    boot::<4>(slice, 6)
+}
+
+/// A `size` by `size` initial slice with about a third of the cubes active.
+pub fn generate(rng: &mut Random, size: usize) -> Generated {
+    let size = size.max(1);
+    let mut slice = Grid::from_fn(size, size, |_, _| {
+        if rng.gen_bool(0.35) {
+            CubeState::Active
+        } else {
+            CubeState::Inactive
+        }
+    });
+    // The boot process needs something to start from
+    slice[(rng.gen_range(0..size), rng.gen_range(0..size))] = CubeState::Active;
+
+    let input = slice.render(|&cube| if cube == CubeState::Active { '#' } else { '.' });
+    Generated {
+        input: input.trim_end().to_string(),
+        answer1: None,
+        answer2: None,
+    }
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+    type Puzzle = Grid<CubeState>;
+
+    fn parse_lines(lines: impl Iterator<Item = String>) -> Result<Self::Puzzle, ParseError> {
+        parse_input(lines)
+    }
+
+    fn part1(slice: &Self::Puzzle) -> Option<String> {
+        Some(star_one(slice).to_string())
+    }
+
+    fn part2(slice: &Self::Puzzle) -> Option<String> {
+        Some(star_two(slice).to_string())
+    }
+
+    fn generate(rng: &mut Random, size: usize) -> Option<Generated> {
+        Some(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use proptest::prelude::*;
+
+    use super::CubeState;
+    use crate::grid::Grid;
+
+    /// Initial slices of up to 8 by 8 cubes.
+    fn slice() -> impl Strategy<Value = Grid<CubeState>> {
+        (1..=8usize, 1..=8usize).prop_flat_map(|(width, height)| {
+            prop::collection::vec(any::<bool>(), width * height).prop_map(move |cubes| {
+                Grid::from_fn(width, height, |x, y| {
+                    if cubes[y * width + x] {
+                        CubeState::Active
+                    } else {
+                        CubeState::Inactive
+                    }
+                })
+            })
+        })
+    }
+
+    static TEST_DATA: &str = ".#.
+..#
+###";
+
+    #[test]
+    fn test_star_one() {
+        let slice = super::parse_input(TEST_DATA.lines()).expect("Error in test data");
+
+        let ans = super::star_one(&slice);
+        assert_eq!(ans, 112);
+    }
+
+    #[test]
+    fn test_star_two() {
+        let slice = super::parse_input(TEST_DATA.lines()).expect("Error in test data");
+
+        let ans = super::star_two(&slice);
+        assert_eq!(ans, 848);
+    }
+
+    #[test]
+    fn test_display() {
+        let slice = super::parse_input(TEST_DATA.lines()).expect("Error in test data");
+        let mut dimension = super::pocket_dimension::<3>(&slice);
+        dimension.step();
+
+        assert_eq!(dimension.to_string(), "z=-1\n#..\n..#\n.#.\n\nz=0\n#.#\n.##\n.#.\n\nz=1\n#..\n..#\n.#.\n");
+    }
+
+    #[test]
+    fn test_other_dimensions() {
+        let slice = super::parse_input(TEST_DATA.lines()).expect("Error in test data");
+
+        // In 2D the example is the glider from the Game of Life
+        assert_eq!(super::boot::<2>(&slice, 6), 5);
+        assert!(super::boot::<5>(&slice, 1) > 0);
+    }
+
+    #[test]
+    fn test_generate() {
+        let generated = crate::generate::check::<super::Day17>(0, 5);
+        assert_eq!(generated.input.lines().count(), 5);
+        crate::generate::check::<super::Day17>(1, 1);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_3d_is_4d_slice(slice in slice()) {
+            // Nothing is active outside w = 0 yet, so after one cycle that
+            // slice of 4D looks just like 3D
+            let mut three = super::pocket_dimension::<3>(&slice);
+            let mut four = super::pocket_dimension::<4>(&slice);
+            three.step();
+            four.step();
+
+            let expected: HashSet<[isize; 3]> = three.alive().copied().collect();
+            let w_slice = |w: isize| -> HashSet<[isize; 3]> {
+                four.alive().filter(|cube| cube[3] == w).map(|&[x, y, z, _]| [x, y, z]).collect()
+            };
+            prop_assert_eq!(w_slice(0), expected);
+            prop_assert_eq!(w_slice(1), w_slice(-1));
+        }
+    }
+}
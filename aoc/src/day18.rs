@@ -0,0 +1,399 @@
+use std::cmp::Ordering;
+use std::vec::Vec;
+use rand::Rng;
+
+use crate::error::ParseError;
+use crate::generate::{Generated, Random};
+use crate::int::{self, Int, Overflow};
+use crate::solution::Solution;
+
+#[derive(Debug, PartialEq)]
+pub enum Token {
+    Number(isize),
+    Addition,
+    Multiplication,
+    LeftParenthesis,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Expression {
+    pub advanced: bool,
+    pub precedence: Vec<Vec<Token>>,
+    pub tokens: Vec<Token>,
+}
+
+impl Expression {
+    pub fn new(advanced: bool) -> Self {
+        if !advanced {
+            Expression {
+                advanced,
+                precedence: vec![vec![Token::Addition,Token::Multiplication]],
+                tokens: vec![],
+            }
+        }
+        else {
+            Expression {
+                advanced,
+                precedence: vec![vec![Token::Multiplication],vec![Token::Addition]],
+                tokens: vec![],
+            }
+        }
+    }
+
+    fn compare_precedence(&self, left: &Token, right: &Token) -> Ordering {
+        // TODO: probably should do caching
+        let left_idx = self.precedence.iter().enumerate().find_map(|(idx, vec)| if vec.contains(left) { Some(idx) } else { None }).unwrap();
+        let right_idx = self.precedence.iter().enumerate().find_map(|(idx, vec)| if vec.contains(right) { Some(idx) } else { None }).unwrap();
+        left_idx.cmp(&right_idx)
+    }
+
+    pub fn from_line(line: &str, advanced: bool) -> Result<Self, ParseError>
+    {
+        let invalid = |reason| ParseError::new(Day18::DAY, line, reason);
+        let mut result = Expression::new(advanced);
+
+        // Cheat and ensure every token is separated by whitespace
+        let corrected = line.replace('(', "( ").replace(')', " )");
+        let mut tokens = corrected.split_ascii_whitespace();
+        let mut operstack: Vec<Token> = vec![];
+    
+        // Shunting Yard algorithm by Dijkstra
+        loop {
+            let t = tokens.next();
+            match t {
+                Some(raw_oper) if raw_oper == "+" || raw_oper == "*" => {
+                    let oper = match raw_oper {
+                        "+" => Token::Addition,
+                        "*" => Token::Multiplication,
+                        _ => unreachable!(),
+                    };
+                    while !operstack.is_empty() {
+                        let last = operstack.last().unwrap();
+                        if *last == Token::LeftParenthesis {
+                            break;
+                        }
+                        match result.compare_precedence(last, &oper) {
+                            Ordering::Less => break,
+                            // We only have left-associative operators + and * so both greater and equal cases are the same
+                            _ => {
+                                let last = operstack.pop().unwrap();
+                                result.tokens.push(last);
+                            }
+                        }
+                    }
+                    operstack.push(oper);
+                },
+                Some("(") => operstack.push(Token::LeftParenthesis),
+                Some(")") => {
+                    while !operstack.is_empty() && operstack.last().unwrap() != &Token::LeftParenthesis {
+                        let last = operstack.pop().unwrap();
+                        result.tokens.push(last);
+                    }
+                    if operstack.is_empty() {
+                        return Err(invalid("unbalanced ')'"));
+                    }
+                    if operstack.last().unwrap() == &Token::LeftParenthesis {
+                        operstack.pop();
+                    }
+                },
+                Some(raw_number) => {
+                    if let Ok(number) = raw_number.parse::<isize>() {
+                        result.tokens.push(Token::Number(number));
+                    } else {
+                        return Err(invalid("invalid number"));
+                    }
+                },
+                None => break,
+            };
+        }
+
+        while let Some(last) = operstack.pop() {
+            
+            if last == Token::LeftParenthesis {
+                return Err(invalid("unbalanced '('"));
+            }
+            result.tokens.push(last);
+        }
+
+        // Every operator needs two operands, leaving a single result
+        let mut depth: usize = 0;
+        for token in result.tokens.iter() {
+            match token {
+                Token::Number(_) => depth += 1,
+                _ if depth < 2 => return Err(invalid("missing operand")),
+                _ => depth -= 1,
+            }
+        }
+        if depth != 1 {
+            return Err(invalid("missing operator"));
+        }
+
+        Ok(result)
+    }
+    
+    pub fn calculate(&self) -> Result<Int, Overflow> {
+        let mut operands: Vec<Int> = vec![];
+        let mut generator = self.tokens.iter();
+        loop {
+            let t = generator.next();
+            match t {
+                Some(Token::Number(val)) => {
+                    operands.push(int::from(*val)?);
+                },
+                Some(Token::Addition) => {
+                    let left_oper = operands.pop().unwrap();
+                    let right_oper = operands.pop().unwrap();
+                    // Cannot inline the double pop due to the fact that operands cannot be borrowed twice in the same call
+                    operands.push(int::add(&left_oper, &right_oper)?);
+                },
+                Some(Token::Multiplication) => {
+                    let left_oper = operands.pop().unwrap();
+                    let right_oper = operands.pop().unwrap();
+                    // Cannot inline the double pop due to the fact that operands cannot be borrowed twice in the same call
+                    operands.push(int::mul(&left_oper, &right_oper)?);
+                },
+                Some(_) => unreachable!(),
+                None => break,
+            }
+        }
+
+        assert_eq!(operands.len(), 1);
+        Ok(operands.remove(0))
+    }
+}
+
+pub fn sum_expressions(expressions: &[Expression]) -> Result<Int, Overflow> {
+    expressions.iter().try_fold(Int::from(0), |s, x| int::add(&s, &x.calculate()?))
+}
+
+/// A random expression as text, with its value evaluated left to right and
+/// with addition first. `None` when either value gets too big.
+fn random_expression(rng: &mut Random, depth: usize) -> Option<(String, isize, isize)> {
+    let mut text = String::new();
+    // Left to right, and the products of sums
+    let mut simple: isize = 0;
+    let (mut product, mut sum): (isize, isize) = (1, 0);
+    for i in 0..rng.gen_range(2..=5) {
+        let add = i == 0 || rng.gen_bool(0.5);
+        if i > 0 {
+            text.push_str(if add { " + " } else { " * " });
+        }
+        let (term, value_simple, value_advanced) = if depth > 0 && rng.gen_bool(0.3) {
+            let (inner, simple, advanced) = random_expression(rng, depth - 1)?;
+            (format!("({})", inner), simple, advanced)
+        } else {
+            let number = rng.gen_range(1..=9);
+            (number.to_string(), number, number)
+        };
+        text.push_str(&term);
+
+        simple = if add {
+            simple.checked_add(value_simple)?
+        } else {
+            simple.checked_mul(value_simple)?
+        };
+        if !add {
+            product = product.checked_mul(sum)?;
+            sum = 0;
+        }
+        sum = sum.checked_add(value_advanced)?;
+    }
+    let advanced = product.checked_mul(sum)?;
+
+    if simple > 1_000_000_000_000 || advanced > 1_000_000_000_000 {
+        return None;
+    }
+    Some((text, simple, advanced))
+}
+
+/// `size` random expressions with up to three levels of parentheses.
+pub fn generate(rng: &mut Random, size: usize) -> Generated {
+    let mut lines = vec![];
+    let (mut simple, mut advanced) = (0, 0);
+    while lines.len() < size.max(1) {
+        if let Some((line, value_simple, value_advanced)) = random_expression(rng, 3) {
+            lines.push(line);
+            simple += value_simple;
+            advanced += value_advanced;
+        }
+    }
+
+    Generated {
+        input: lines.join("\n"),
+        answer1: Some(simple.to_string()),
+        answer2: Some(advanced.to_string()),
+    }
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+    // Operator precedence differs per part, so keep the expressions parsed both ways
+    type Puzzle = (Vec<Expression>, Vec<Expression>);
+
+    fn parse_lines(lines: impl Iterator<Item = String>) -> Result<Self::Puzzle, ParseError> {
+        let mut simple = vec![];
+        let mut advanced = vec![];
+        for (i, x) in lines.enumerate() {
+            simple.push(Expression::from_line(&x, false).map_err(|e| e.at_line(i + 1))?);
+            advanced.push(Expression::from_line(&x, true).map_err(|e| e.at_line(i + 1))?);
+        }
+
+        Ok((simple, advanced))
+    }
+
+    fn part1((expressions, _): &Self::Puzzle) -> Option<String> {
+        int::answer(Self::DAY, sum_expressions(expressions))
+    }
+
+    fn part2((_, expressions): &Self::Puzzle) -> Option<String> {
+        int::answer(Self::DAY, sum_expressions(expressions))
+    }
+
+    fn generate(rng: &mut Random, size: usize) -> Option<Generated> {
+        Some(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::iter::Peekable;
+    use std::str::Chars;
+
+    use proptest::prelude::*;
+
+    use super::Expression;
+
+    static TEST_DATA: &str = "1 + 2 * 3 + 4 * 5 + 6
+1 + (2 * 3) + (4 * (5 + 6))
+2 * 3 + (4 * 5)
+5 + (8 * 3 + 9 + 3 * 4 * 3)
+5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))
+((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2";
+
+    #[test]
+    fn test_star_one() {
+        let expressions: Vec<super::Expression> = TEST_DATA
+            .lines()
+            .map(|x| super::Expression::from_line(x, false).expect("Invalid operation in test data"))
+            .collect();
+
+        assert_eq!(expressions[0].tokens[0], super::Token::Number(1));
+        assert_eq!(expressions[0].tokens[1], super::Token::Number(2));
+        assert_eq!(expressions[0].tokens[2], super::Token::Addition);
+        assert_eq!(expressions[0].tokens[3], super::Token::Number(3));
+        assert_eq!(expressions[0].tokens[4], super::Token::Multiplication);
+        assert_eq!(expressions[0].calculate(), Ok(71.into()));
+        assert_eq!(expressions[1].calculate(), Ok(51.into()));
+
+        let ans = super::sum_expressions(&expressions);
+        assert_eq!(ans, Ok((71 + 51 + 26 + 437 + 12240 + 13632).into()));
+    }
+
+    #[test]
+    fn test_star_two() {
+        let expressions: Vec<super::Expression> = TEST_DATA
+            .lines()
+            .map(|x| super::Expression::from_line(x, true).expect("Invalid operation in test data"))
+            .collect();
+
+        assert_eq!(expressions[0].calculate(), Ok(231.into()));
+        assert_eq!(expressions[1].calculate(), Ok(51.into()));
+        assert_eq!(expressions[2].calculate(), Ok(46.into()));
+
+        let ans = super::sum_expressions(&expressions);
+        assert_eq!(ans, Ok((231 + 51 + 46 + 1445 + 669060 + 23340).into()));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let line = vec!["9"; 20].join(" * ");
+        let expression = super::Expression::from_line(&line, false).expect("Invalid test data");
+        let ans = expression.calculate().map(|x| x.to_string());
+        if cfg!(feature = "bigint") {
+            assert_eq!(ans, Ok(String::from("12157665459056928801")));
+        } else {
+            assert_eq!(ans, Err(crate::int::Overflow));
+        }
+    }
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..5 {
+            crate::generate::check::<super::Day18>(seed, 100);
+        }
+    }
+
+    /// Evaluate by recursive descent, with addition before multiplication if
+    /// `advanced`, to compare with the shunting yard.
+    fn reference(chars: &mut Peekable<Chars>, advanced: bool) -> isize {
+        fn term(chars: &mut Peekable<Chars>, advanced: bool) -> isize {
+            match chars.next() {
+                Some('(') => {
+                    let value = reference(chars, advanced);
+                    assert_eq!(chars.next(), Some(')'));
+                    value
+                }
+                Some(digit) => digit.to_digit(10).expect("Not a digit") as isize,
+                None => panic!("Missing term"),
+            }
+        }
+        fn sum(chars: &mut Peekable<Chars>, advanced: bool) -> isize {
+            let mut value = term(chars, advanced);
+            while chars.peek() == Some(&'+') {
+                chars.next();
+                value += term(chars, advanced);
+            }
+            value
+        }
+
+        if advanced {
+            let mut value = sum(chars, advanced);
+            while chars.peek() == Some(&'*') {
+                chars.next();
+                value *= sum(chars, advanced);
+            }
+            value
+        } else {
+            let mut value = term(chars, advanced);
+            while let Some(op) = chars.next_if(|&ch| ch == '+' || ch == '*') {
+                let right = term(chars, advanced);
+                if op == '+' {
+                    value += right;
+                } else {
+                    value *= right;
+                }
+            }
+            value
+        }
+    }
+
+    /// Expressions of single digits with up to 16 of them, so the values fit.
+    fn expression() -> impl Strategy<Value = String> {
+        let digit = (1..=9u8).prop_map(|x| x.to_string());
+        digit.prop_recursive(2, 16, 4, |inner| {
+            (prop::collection::vec(inner, 2..=4), prop::collection::vec(any::<bool>(), 3)).prop_map(|(terms, ops)| {
+                let mut text = format!("({}", terms[0]);
+                for (term, &add) in terms[1..].iter().zip(ops.iter()) {
+                    text.push_str(if add { " + " } else { " * " });
+                    text.push_str(term);
+                }
+                text.push(')');
+                text
+            })
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn prop_calculate(line in expression()) {
+            let compact = line.replace(' ', "");
+            for &advanced in [false, true].iter() {
+                let expression = Expression::from_line(&line, advanced).expect("Invalid expression");
+                let value = reference(&mut compact.chars().peekable(), advanced);
+                prop_assert_eq!(expression.calculate(), crate::int::from(value));
+            }
+        }
+    }
+}
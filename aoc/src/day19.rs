@@ -0,0 +1,485 @@
+use std::collections::HashMap;
+use std::vec::Vec;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use regex::Regex;
+
+use crate::error::ParseError;
+use crate::generate::{Generated, Random};
+use crate::solution::{Solution, Variant};
+
+#[derive(Debug)]
+pub enum Rule {
+    InOrder(Vec<usize>),
+    Either(Vec<usize>, Vec<usize>),
+    Literal(String),
+}
+
+#[derive(Debug, Default)]
+pub struct SatelliteMessages {
+    pub rules: HashMap<usize, Rule>,
+    pub messages: Vec<String>,
+}
+
+impl SatelliteMessages {
+    pub fn new() -> Self {
+        SatelliteMessages {
+            rules: HashMap::new(),
+            messages: vec![],
+        }
+    }
+
+    pub fn parse_rule(&mut self, line: &str) -> Result<(), ParseError> {
+        let invalid = |reason| ParseError::new(Day19::DAY, line, reason);
+        let parse_list_of_ints = |list: &str| -> Result<Vec<usize>, ParseError> {
+            list.trim()
+                .split_ascii_whitespace()
+                .map(|n| n.parse::<usize>().map_err(|_| invalid("invalid rule reference")))
+                .collect()
+        };
+
+        let mut split = line.split(':');
+        let index = split
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .parse::<usize>()
+            .map_err(|_| invalid("invalid rule number"))?;
+        let raw_rule = split.next().ok_or_else(|| invalid("missing ':' after rule number"))?.trim();
+        if raw_rule.contains('"') {
+            let literal = raw_rule.trim_matches('\"');
+            if literal.is_empty() || !literal.chars().all(char::is_alphanumeric) {
+                return Err(invalid("literal should only contain letters or digits"));
+            }
+            self.rules.insert(
+                index,
+                Rule::Literal(String::from(literal)),
+            );
+        } else if raw_rule.contains('|') {
+            let mut or = raw_rule.split('|');
+            let left: Vec<usize> = parse_list_of_ints(or.next().unwrap_or_default())?;
+            let right: Vec<usize> = parse_list_of_ints(or.next().unwrap_or_default())?;
+            if or.next().is_some() {
+                return Err(invalid("more than one '|' in rule"));
+            }
+            self.rules.insert(index, Rule::Either(left, right));
+        } else {
+            let ordered: Vec<usize> = parse_list_of_ints(raw_rule)?;
+            self.rules.insert(index, Rule::InOrder(ordered));
+        }
+
+        Ok(())
+    }
+
+    pub fn from_lines(lines: impl Iterator<Item = String>) -> Result<Self, ParseError> {
+        enum ParseState {
+            Rules,
+            Messages,
+        }
+
+        let mut sat = SatelliteMessages::new();
+        let mut state = ParseState::Rules;
+        for (i, line) in lines.enumerate() {
+            if line.trim().is_empty() {
+                state = ParseState::Messages;
+                continue;
+            }
+
+            match state {
+                ParseState::Rules => sat.parse_rule(&line).map_err(|e| e.at_line(i + 1))?,
+                ParseState::Messages => sat.messages.push(String::from(line.trim())),
+            }
+        }
+
+        // Check that every rule only refers to rules that exist
+        if !sat.rules.contains_key(&0) {
+            return Err(ParseError::new(Day19::DAY, "", "missing rule 0"));
+        }
+        for (index, rule) in sat.rules.iter() {
+            let references = match rule {
+                Rule::InOrder(vec) => vec.clone(),
+                Rule::Either(left, right) => [left.as_slice(), right.as_slice()].concat(),
+                Rule::Literal(_) => vec![],
+            };
+            if let Some(missing) = references.iter().find(|x| !sat.rules.contains_key(x)) {
+                return Err(ParseError::new(
+                    Day19::DAY,
+                    &index.to_string(),
+                    format!("rule refers to missing rule {}", missing),
+                ));
+            }
+        }
+
+        Ok(sat)
+    }
+
+    fn build_regexp(&self, index: usize, result: &mut String, advanced: bool) {
+        if advanced && index == 8 {
+            result.push('(');
+            self.build_regexp(42, result, true);
+            result.push_str(")+");
+            return;
+        } else if advanced && index == 11 {
+            result.push('(');
+            // The ugly way -- try to preplan the amount of repetition.
+            // Max input length = 97, both rules 42 and 31 matches minimum of 5 characters,
+            // minimum repetition = 1 so we need to plan for 9 combinations (42,31) , (42,42,31,31) , etc.
+            for rep in 1..=9 {
+                for _ in 0..rep {
+                    self.build_regexp(42, result, true);
+                }
+                for _ in 0..rep {
+                    self.build_regexp(31, result, true);
+                }
+                if rep != 9 {
+                    result.push('|');
+                }
+            }
+            result.push(')');
+            return;
+        }
+
+        match self.rules.get(&index).unwrap() {
+            Rule::InOrder(vec) => {
+                for idx in vec {
+                    self.build_regexp(*idx, result, advanced);
+                }
+            },
+            Rule::Either(left, right) => {
+                result.push('(');
+                for idx in left {
+                    self.build_regexp(*idx, result, advanced);
+                }
+                result.push('|');
+                for idx in right {
+                    self.build_regexp(*idx, result, advanced);
+                }
+                result.push(')');
+            },
+            Rule::Literal(ch) => {
+                result.push_str(ch);
+            }
+        }
+    }
+
+    pub fn matching_messages(&self, advanced: bool) -> usize {
+        let mut regexp = String::from("^");
+        self.build_regexp(0, &mut regexp, advanced);
+        regexp.push('$');
+        let re = Regex::new(&regexp).unwrap();
+        let mut result: usize = 0;
+
+        for msg in self.messages.iter() {
+            if re.is_match(msg) {
+                result += 1;
+            }
+        }
+
+        result
+    }
+
+    // All positions in msg where a match of the given rules, in order, starting at pos can end
+    fn match_sequence(&self, sequence: &[usize], msg: &str, pos: usize, advanced: bool) -> Vec<usize> {
+        sequence.iter().fold(vec![pos], |ends, index| {
+            ends.iter()
+                .flat_map(|&end| self.match_rule(*index, msg, end, advanced))
+                .collect()
+        })
+    }
+
+    fn match_rule(&self, index: usize, msg: &str, pos: usize, advanced: bool) -> Vec<usize> {
+        // Every rule matches at least one character, so the loops in rules 8 and 11 end
+        if advanced && index == 8 {
+            let mut ends = self.match_sequence(&[42], msg, pos, true);
+            ends.extend(self.match_sequence(&[42, 8], msg, pos, true));
+            return ends;
+        } else if advanced && index == 11 {
+            let mut ends = self.match_sequence(&[42, 31], msg, pos, true);
+            ends.extend(self.match_sequence(&[42, 11, 31], msg, pos, true));
+            return ends;
+        }
+
+        match self.rules.get(&index).unwrap() {
+            Rule::InOrder(vec) => self.match_sequence(vec, msg, pos, advanced),
+            Rule::Either(left, right) => {
+                let mut ends = self.match_sequence(left, msg, pos, advanced);
+                ends.extend(self.match_sequence(right, msg, pos, advanced));
+                ends
+            },
+            Rule::Literal(literal) => {
+                if msg[pos..].starts_with(literal.as_str()) {
+                    vec![pos + literal.len()]
+                } else {
+                    vec![]
+                }
+            }
+        }
+    }
+
+    /// Same as `matching_messages`, but matching the rules directly instead
+    /// of building a regular expression from them.
+    pub fn matching_messages_recursive(&self, advanced: bool) -> usize {
+        self.messages
+            .iter()
+            .filter(|msg| self.match_rule(0, msg, 0, advanced).contains(&msg.len()))
+            .count()
+    }
+}
+
+pub fn star_one(messages: &SatelliteMessages) -> usize {
+    messages.matching_messages(false)
+}
+
+pub fn star_two(messages: &SatelliteMessages) -> usize {
+    messages.matching_messages(true)
+}
+
+/// Add rules matching exactly `chunks`, which all share their first `depth`
+/// letters, like a trie: every rule branches on the next letter. Gives the
+/// number of the rule matching the remaining letters.
+fn add_trie_rule(
+    chunks: &[Vec<u8>],
+    depth: usize,
+    ids: &mut Vec<usize>,
+    literals: &[usize; 2],
+    rules: &mut Vec<String>,
+) -> usize {
+    let mut branches = vec![];
+    for (i, letter) in [b'a', b'b'].iter().enumerate() {
+        let rest: Vec<Vec<u8>> = chunks.iter().filter(|chunk| chunk[depth] == *letter).cloned().collect();
+        if rest.is_empty() {
+            continue;
+        }
+        let mut branch = literals[i].to_string();
+        if depth + 1 < rest[0].len() {
+            let next = add_trie_rule(&rest, depth + 1, ids, literals, rules);
+            branch.push_str(&format!(" {}", next));
+        }
+        branches.push(branch);
+    }
+
+    let id = ids.pop().unwrap();
+    rules.push(format!("{}: {}", id, branches.join(" | ")));
+    id
+}
+
+/// `size` messages for rules where 42 and 31 each match half of all chunks of
+/// 3 or 4 letters. Messages are built chunk by chunk, so which ones match
+/// follows from the rules the chunks came from.
+pub fn generate(rng: &mut Random, size: usize) -> Generated {
+    let chunk_len = rng.gen_range(3..=4);
+    let mut chunks: Vec<Vec<u8>> = (0..1 << chunk_len)
+        .map(|bits: usize| (0..chunk_len).map(|i| if bits >> i & 1 == 1 { b'b' } else { b'a' }).collect())
+        .collect();
+    chunks.shuffle(rng);
+    let split = rng.gen_range(1..chunks.len());
+    let (chunks_42, chunks_31) = chunks.split_at(split);
+
+    // Numbers for the helper rules, out of the way of the fixed ones
+    let mut ids: Vec<usize> = (1..200).filter(|id| ![8, 11, 31, 42].contains(id)).collect();
+    ids.shuffle(rng);
+    let literals = [ids.pop().unwrap(), ids.pop().unwrap()];
+    let mut rules = vec![
+        String::from("0: 8 11"),
+        String::from("8: 42"),
+        String::from("11: 42 31"),
+        format!("{}: \"a\"", literals[0]),
+        format!("{}: \"b\"", literals[1]),
+    ];
+    let rule_42 = add_trie_rule(chunks_42, 0, &mut ids, &literals, &mut rules);
+    let rule_31 = add_trie_rule(chunks_31, 0, &mut ids, &literals, &mut rules);
+    rules.push(format!("42: {}", rule_42));
+    rules.push(format!("31: {}", rule_31));
+    rules.shuffle(rng);
+
+    let mut messages = vec![];
+    let (mut nr_one, mut nr_two) = (0, 0);
+    for _ in 0..size.max(1) {
+        // Mostly close to matching, with a chunk swapped for the other rule
+        // or a letter too many now and then
+        let nr_42 = rng.gen_range(1..=6);
+        let nr_31 = rng.gen_range(0..=4);
+        let mut from_42: Vec<bool> = (0..nr_42 + nr_31).map(|i| i < nr_42).collect();
+        if rng.gen_bool(0.3) {
+            let i = rng.gen_range(0..from_42.len());
+            from_42[i] = !from_42[i];
+        }
+        let mut message = String::new();
+        for &is_42 in from_42.iter() {
+            let chunk = if is_42 { chunks_42.choose(rng) } else { chunks_31.choose(rng) };
+            message.push_str(std::str::from_utf8(chunk.unwrap()).unwrap());
+        }
+        let extra_letter = rng.gen_bool(0.1);
+        if extra_letter {
+            message.push(*['a', 'b'].choose(rng).unwrap());
+        }
+        messages.push(message);
+
+        if extra_letter {
+            continue;
+        }
+        let leading_42 = from_42.iter().take_while(|&&x| x).count();
+        let trailing_31 = from_42.iter().rev().take_while(|&&x| !x).count();
+        if from_42 == [true, true, false] {
+            nr_one += 1;
+        }
+        if leading_42 + trailing_31 == from_42.len() && trailing_31 >= 1 && leading_42 > trailing_31 {
+            nr_two += 1;
+        }
+    }
+
+    Generated {
+        input: format!("{}\n\n{}", rules.join("\n"), messages.join("\n")),
+        answer1: Some(nr_one.to_string()),
+        answer2: Some(nr_two.to_string()),
+    }
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u8 = 19;
+    type Puzzle = SatelliteMessages;
+
+    fn parse_lines(lines: impl Iterator<Item = String>) -> Result<Self::Puzzle, ParseError> {
+        SatelliteMessages::from_lines(lines)
+    }
+
+    fn part1(messages: &Self::Puzzle) -> Option<String> {
+        Some(star_one(messages).to_string())
+    }
+
+    fn part2(messages: &Self::Puzzle) -> Option<String> {
+        Some(star_two(messages).to_string())
+    }
+
+    fn variants(part: u8) -> Vec<Variant<Self::Puzzle>> {
+        let variants: Vec<Variant<Self::Puzzle>> = match part {
+            1 => vec![
+                ("regex", Self::part1),
+                ("recursive", |messages| Some(messages.matching_messages_recursive(false).to_string())),
+            ],
+            _ => vec![
+                ("regex", Self::part2),
+                ("recursive", |messages| Some(messages.matching_messages_recursive(true).to_string())),
+            ],
+        };
+        variants
+    }
+
+    fn generate(rng: &mut Random, size: usize) -> Option<Generated> {
+        Some(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    static TEST_DATA_1: &str = "0: 1 2
+1: \"a\"
+2: 1 3 | 3 1
+3: \"b\"
+
+aab
+abb
+aba
+";
+
+    static TEST_DATA_2: &str = "0: 4 1 5
+1: 2 3 | 3 2
+2: 4 4 | 5 5
+3: 4 5 | 5 4
+4: \"a\"
+5: \"b\"
+
+ababbb
+bababa
+abbbab
+aaabbb
+aaaabbb";
+
+    #[test]
+    fn test_star_one() {
+        let lines = TEST_DATA_1.lines().map(String::from);
+        let messages = super::SatelliteMessages::from_lines(lines).expect("Invalid test data");
+        let ans = super::star_one(&messages);
+        assert_eq!(ans, 2);
+
+        let lines = TEST_DATA_2.lines().map(String::from);
+        let messages = super::SatelliteMessages::from_lines(lines).expect("Invalid test data");
+        let ans = super::star_one(&messages);
+        assert_eq!(ans, 2);
+        assert_eq!(messages.matching_messages_recursive(false), 2);
+    }
+
+    static TEST_DATA_3: &str = "42: 9 14 | 10 1
+9: 14 27 | 1 26
+10: 23 14 | 28 1
+1: \"a\"
+11: 42 31
+5: 1 14 | 15 1
+19: 14 1 | 14 14
+12: 24 14 | 19 1
+16: 15 1 | 14 14
+31: 14 17 | 1 13
+6: 14 14 | 1 14
+2: 1 24 | 14 4
+0: 8 11
+13: 14 3 | 1 12
+15: 1 | 14
+17: 14 2 | 1 7
+23: 25 1 | 22 14
+28: 16 1
+4: 1 1
+20: 14 14 | 1 15
+3: 5 14 | 16 1
+27: 1 6 | 14 18
+14: \"b\"
+21: 14 1 | 1 14
+25: 1 1 | 1 14
+22: 14 14
+8: 42
+26: 14 22 | 1 20
+18: 15 15
+7: 14 5 | 1 21
+24: 14 1
+
+abbbbbabbbaaaababbaabbbbabababbbabbbbbbabaaaa
+bbabbbbaabaabba
+babbbbaabbbbbabbbbbbaabaaabaaa
+aaabbbbbbaaaabaababaabababbabaaabbababababaaa
+bbbbbbbaaaabbbbaaabbabaaa
+bbbababbbbaaaaaaaabbababaaababaabab
+ababaaaaaabaaab
+ababaaaaabbbaba
+baabbaaaabbaaaababbaababb
+abbbbabbbbaaaababbbbbbaaaababb
+aaaaabbaabaaaaababaa
+aaaabbaaaabbaaa
+aaaabbaabbaaaaaaabbbabbbaaabbaabaaa
+babaaabbbaaabaababbaabababaaab
+aabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba";
+    
+    #[test]
+    fn test_star_two() {
+        let lines = TEST_DATA_3.lines().map(String::from);
+        let messages = super::SatelliteMessages::from_lines(lines).expect("Invalid test data");
+        let ans = super::star_one(&messages);
+        assert_eq!(ans, 3);
+
+        let lines = TEST_DATA_3.lines().map(String::from);
+        let messages = super::SatelliteMessages::from_lines(lines).expect("Invalid test data");
+        let ans = super::star_two(&messages);
+        assert_eq!(ans, 12);
+        assert_eq!(messages.matching_messages(false), messages.matching_messages_recursive(false));
+        assert_eq!(messages.matching_messages_recursive(true), 12);
+    }
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..5 {
+            let generated = crate::generate::check::<super::Day19>(seed, 100);
+            let messages = <super::Day19 as crate::Solution>::parse(&generated.input).expect("Invalid generated input");
+            assert_eq!(messages.matching_messages_recursive(true).to_string(), generated.answer2.unwrap());
+        }
+    }
+}
@@ -0,0 +1,926 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt;
+use std::iter::Peekable;
+use std::vec::Vec;
+use log::{debug, log_enabled, Level};
+use rand::seq::SliceRandom;
+use rand::Rng;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::error::ParseError;
+use crate::generate::{Generated, Random};
+use crate::grid::Grid;
+use crate::int::{self, Int, Overflow};
+use crate::solution::Solution;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Pixel {
+    Off = 0,
+    On = 1,
+    DontCare = 2,
+    Monster = 3,
+}
+
+impl From<Pixel> for char {
+    fn from(pixel: Pixel) -> char {
+        match pixel {
+            Pixel::On => '#',
+            Pixel::Off => '.',
+            Pixel::DontCare => '/',
+            Pixel::Monster => 'O',
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum Symmetry {
+    Identity0,
+    Rotate090,
+    Rotate180,
+    Rotate270,
+    FlipNSIdn,
+    FlipNS090,
+    FlipNS180,
+    FlipNS270,
+}
+
+impl Symmetry {
+    /// The symmetry that undoes this one.
+    pub fn inverse(&self) -> Symmetry {
+        // Flipping first and rotating after is its own inverse
+        match self {
+            Symmetry::Rotate090 => Symmetry::Rotate270,
+            Symmetry::Rotate270 => Symmetry::Rotate090,
+            other => *other,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Tile {
+    pub id: usize, // Better Option<usize> but too many edge cases for this assignment
+    pub image: Grid<Pixel>,
+    pub orientation: Symmetry,
+    pub borders: HashMap<Symmetry, HashMap<Direction, usize>>,
+}
+
+impl std::fmt::Display for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Tile {} ({:?}):", self.id, self.orientation)?;
+        writeln!(f, "{}", self.image)
+
+        // let symmetries = vec![
+        //     Symmetry::Identity0,
+        //     Symmetry::Rotate090,
+        //     Symmetry::Rotate180,
+        //     Symmetry::Rotate270,
+        //     Symmetry::FlipNSIdn,
+        //     Symmetry::FlipNS090,
+        //     Symmetry::FlipNS180,
+        //     Symmetry::FlipNS270,
+        // ];
+        // for sym in symmetries {
+        //     if let Some(br) = self.borders.get(&sym) {
+        //         writeln!(
+        //             f,
+        //             "{:25?} N {:4} E {:4} S {:4} W {:4}",
+        //             sym,
+        //             br.get(&Direction::North).unwrap(),
+        //             br.get(&Direction::East).unwrap(),
+        //             br.get(&Direction::South).unwrap(),
+        //             br.get(&Direction::West).unwrap()
+        //         )?;
+        //     }
+        // }
+
+        // writeln!(f)
+    }
+}
+
+impl Default for Tile {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tile {
+    pub fn new() -> Self {
+        Tile {
+            id: 0,
+            image: Grid::new(0, 0, Pixel::Off),
+            orientation: Symmetry::Identity0,
+            borders: HashMap::new(),
+        }
+    }
+
+    pub fn calculate_borders(&self) -> Result<HashMap<Direction, usize>, ParseError> {
+        // Deliberately fixed to 10 positions
+        if self.image.height() != 10 {
+            return Err(ParseError::new(
+                Day20::DAY,
+                &format!("Tile {}", self.id),
+                format!("tile has {} rows instead of 10", self.image.height()),
+            ));
+        }
+
+        let mut result = HashMap::new();
+
+        // North
+        let mut border: usize = 0;
+        for pow in (0..10).rev() {
+            border += (self.image[(9 - pow, 0)] as usize) << pow;
+        }
+        result.insert(Direction::North, border);
+
+        // East
+        let mut border: usize = 0;
+        for pow in (0..10).rev() {
+            border += (self.image[(9, 9 - pow)] as usize) << pow;
+        }
+        result.insert(Direction::East, border);
+
+        // South
+        let mut border: usize = 0;
+        for pow in (0..10).rev() {
+            border += (self.image[(9 - pow, 9)] as usize) << pow;
+        }
+        result.insert(Direction::South, border);
+
+        // West
+        let mut border: usize = 0;
+        for pow in (0..10).rev() {
+            border += (self.image[(0, 9 - pow)] as usize) << pow;
+        }
+        result.insert(Direction::West, border);
+        Ok(result)
+    }
+
+    pub fn rotate90cw(&mut self) {
+        self.image = self.image.rotate_cw();
+        self.orientation = match self.orientation {
+            Symmetry::Identity0 => Symmetry::Rotate090,
+            Symmetry::Rotate090 => Symmetry::Rotate180,
+            Symmetry::Rotate180 => Symmetry::Rotate270,
+            Symmetry::Rotate270 => Symmetry::Identity0,
+            Symmetry::FlipNSIdn => Symmetry::FlipNS090,
+            Symmetry::FlipNS090 => Symmetry::FlipNS180,
+            Symmetry::FlipNS180 => Symmetry::FlipNS270,
+            Symmetry::FlipNS270 => Symmetry::FlipNSIdn,
+        };
+    }
+
+    pub fn flipns(&mut self) {
+        if self.orientation != Symmetry::Identity0 && self.orientation != Symmetry::FlipNSIdn {
+            panic!();
+        }
+
+        self.image = self.image.flip_vertical();
+        self.orientation = match self.orientation {
+            Symmetry::Identity0 => Symmetry::FlipNSIdn,
+            Symmetry::FlipNSIdn => Symmetry::Identity0,
+            _ => unreachable!(),
+        };
+    }
+
+    pub fn rotate_to(&mut self, symmetry: &Symmetry) {
+        // No use in implementing arbitrary from/to
+        assert_eq!(self.orientation, Symmetry::Identity0);
+
+        match symmetry {
+            Symmetry::Identity0 => (),
+            Symmetry::Rotate090 => self.rotate90cw(),
+            Symmetry::Rotate180 => {
+                self.rotate90cw();
+                self.rotate90cw();
+            }
+            Symmetry::Rotate270 => {
+                self.rotate90cw();
+                self.rotate90cw();
+                self.rotate90cw();
+            }
+            Symmetry::FlipNSIdn => self.flipns(),
+            Symmetry::FlipNS090 => {
+                self.flipns();
+                self.rotate90cw();
+            }
+            Symmetry::FlipNS180 => {
+                self.flipns();
+                self.rotate90cw();
+                self.rotate90cw();
+            }
+            Symmetry::FlipNS270 => {
+                self.flipns();
+                self.rotate90cw();
+                self.rotate90cw();
+                self.rotate90cw();
+            }
+        }
+    }
+
+    pub fn from_lines_iter<I>(iter: &mut I) -> Result<Self, ParseError>
+    where
+        I: Iterator,
+        I::Item: Borrow<str>,
+    {
+        // Errors are reported at a line counted from the tile header
+        let mut result = Tile::new();
+        let mut rows = String::new();
+
+        for (i, bline) in iter.enumerate() {
+            let line = bline.borrow();
+            if line.is_empty() {
+                break;
+            }
+
+            if let Some(header) = line.strip_prefix("Tile ") {
+                result.id = header
+                    .strip_suffix(':')
+                    .and_then(|id| id.parse::<usize>().ok())
+                    .ok_or_else(|| ParseError::new(Day20::DAY, line, "invalid tile header").at_line(i + 1))?;
+                continue;
+            }
+            rows.push_str(line);
+            rows.push('\n');
+        }
+
+        result.image = Grid::parse(Day20::DAY, &rows, |ch| match ch {
+            '.' => Ok(Pixel::Off),
+            '#' => Ok(Pixel::On),
+            _ => Err("invalid pixel"),
+        })
+        .map_err(|e| {
+            let line = e.line.unwrap_or(0);
+            e.at_line(line + 1)
+        })?;
+        if result.image.width() != 10 {
+            return Err(ParseError::new(Day20::DAY, "", "tile row is not 10 pixels wide").at_line(2));
+        }
+
+        // Calculate all borders -- TODO calc all in one
+        result
+            .borders
+            .insert(result.orientation, result.calculate_borders()?);
+        result.rotate90cw();
+        result
+            .borders
+            .insert(result.orientation, result.calculate_borders()?);
+        result.rotate90cw();
+        result
+            .borders
+            .insert(result.orientation, result.calculate_borders()?);
+        result.rotate90cw();
+        result
+            .borders
+            .insert(result.orientation, result.calculate_borders()?);
+        result.rotate90cw();
+        result.flipns();
+        result
+            .borders
+            .insert(result.orientation, result.calculate_borders()?);
+        result.rotate90cw();
+        result
+            .borders
+            .insert(result.orientation, result.calculate_borders()?);
+        result.rotate90cw();
+        result
+            .borders
+            .insert(result.orientation, result.calculate_borders()?);
+        result.rotate90cw();
+        result
+            .borders
+            .insert(result.orientation, result.calculate_borders()?);
+        result.rotate90cw();
+        result.flipns();
+        Ok(result)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WorldMap {
+    pub pixels: Grid<Pixel>,
+}
+
+impl std::fmt::Display for WorldMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.pixels)
+    }
+}
+
+impl WorldMap {
+    pub fn from_tilemap(tilemap: &HashMap<(isize, isize), Tile>) -> Self {
+        // Find min/max X and Y in tilemap
+        // TODO: don't loop every time ?
+        let &min_x = tilemap.keys().map(|(x, _)| x).min().unwrap();
+        let &max_x = tilemap.keys().map(|(x, _)| x).max().unwrap();
+        let &min_y = tilemap.keys().map(|(_, y)| y).min().unwrap();
+        let &max_y = tilemap.keys().map(|(_, y)| y).max().unwrap();
+
+        // Tiles are 10x10 so without border 8x8.
+        let width = (max_x - min_x + 1) as usize * 8;
+        let height = (max_y - min_y + 1) as usize * 8;
+        let large_map = Grid::from_fn(width, height, |x, y| {
+            let tile = tilemap.get(&(min_x + (x / 8) as isize, min_y + (y / 8) as isize)).unwrap();
+            tile.image[(x % 8 + 1, y % 8 + 1)]
+        });
+
+        WorldMap {
+            pixels: large_map,
+        }
+    }
+
+    pub fn rotate90cw(&mut self) {
+        self.pixels = self.pixels.rotate_cw();
+    }
+
+    pub fn flipns(&mut self) {
+        self.pixels = self.pixels.flip_vertical();
+    }
+
+    pub fn find_monsters(&mut self) -> bool {
+        let monster_str = "..................#.
+#....##....##....###
+.#..#..#..#..#..#...";
+        let monster = Grid::parse(Day20::DAY, monster_str, |ch| match ch {
+            '#' => Ok(Pixel::On),
+            '.' => Ok(Pixel::DontCare),
+            _ => Err("invalid monster pixel"),
+        })
+        .expect("Invalid monster");
+        let mut found = false;
+        let mut mutated_pixels = self.pixels.clone();
+
+        for y in 0..self.pixels.height() - monster.height() {
+            for x in 0..self.pixels.width() - monster.width() {
+                let complete = monster.positions().all(|(dx, dy)| match monster[(dx, dy)] {
+                    Pixel::DontCare => true,
+                    Pixel::On => self.pixels[(x + dx, y + dy)] == Pixel::On,
+                    _ => unreachable!(),
+                });
+
+                if complete {
+                    // We found a complete monster! Mark it on the mutated map
+                    found = true;
+
+                    for (dx, dy) in monster.positions() {
+                        if monster[(dx, dy)] == Pixel::On {
+                            mutated_pixels[(x + dx, y + dy)] = Pixel::Monster;
+                        }
+                    }
+                }
+            }
+        }
+
+        if found {
+            self.pixels = mutated_pixels;
+        }
+        
+        found
+    }
+
+    pub fn roughness(&self) -> usize {
+        self.pixels.iter().filter(|&&pixel| pixel == Pixel::On).count()
+    }
+}
+
+pub fn parse_input<I>(iter: &mut Peekable<I>) -> Result<Vec<Tile>, ParseError>
+where
+    I: Iterator,
+    I::Item: Borrow<str>,
+{
+    let mut tiles: Vec<Tile> = vec![];
+    let mut line_nr: usize = 0;
+    while iter.peek().is_some() {
+        // Count the lines as they are read, to turn lines within the tile
+        // into lines of the input
+        let start = line_nr;
+        let tile = Tile::from_lines_iter(&mut iter.by_ref().inspect(|_| line_nr += 1));
+        tiles.push(tile.map_err(|e| {
+            let line = e.line.map(|l| start + l).unwrap_or(line_nr);
+            e.at_line(line)
+        })?);
+    }
+    if tiles.is_empty() {
+        return Err(ParseError::new(Day20::DAY, "", "no tiles in input"));
+    }
+    Ok(tiles)
+}
+
+pub fn make_tilemap(tiles: &[Tile]) -> HashMap<(isize, isize), Tile> {
+    let mut tilemap: HashMap<(isize, isize), Tile> = HashMap::new();
+    let mut coords_to_check: Vec<(isize, isize)> = vec![];
+    let directions = [Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West];
+    let symmetries = [Symmetry::Identity0,
+        Symmetry::Rotate090,
+        Symmetry::Rotate180,
+        Symmetry::Rotate270,
+        Symmetry::FlipNSIdn,
+        Symmetry::FlipNS090,
+        Symmetry::FlipNS180,
+        Symmetry::FlipNS270];
+    let mut placed_tiles: Vec<usize> = vec![];
+
+    // Arbitratily begin with the first tile, non-rotated
+    tilemap.insert((0, 0), tiles[0].clone());
+    coords_to_check.push((0, 0));
+    placed_tiles.push(tiles[0].id);
+    while let Some(cur_coord) = coords_to_check.pop() {
+        // Get the tile at cur_coord and determine the tiles next to it
+        
+        // TODO: ugly clone but I need to be able to mutate tilemap below in the loop :-(
+        let cur_tile = tilemap[&cur_coord].clone();
+
+        // Place tiles next to the tile we're examining, if possible
+        for dir in directions.iter() {
+            let (border_to_check, new_coords) = match dir {
+                Direction::North => (Direction::South, (cur_coord.0, cur_coord.1 - 1)),
+                Direction::East => (Direction::West, (cur_coord.0 + 1, cur_coord.1)),
+                Direction::South => (Direction::North, (cur_coord.0, cur_coord.1 + 1)),
+                Direction::West => (Direction::East, (cur_coord.0 - 1, cur_coord.1)),
+            };
+
+            // Don't recheck for coords where a tile is already placed
+            if tilemap.contains_key(&new_coords) {
+                continue;
+            }
+
+            let tile_borders = cur_tile.borders.get(&cur_tile.orientation).unwrap();
+
+            let matches: Vec<(&Tile, &Symmetry)> = tiles
+                .iter()
+                .filter_map(|t| {
+                    symmetries.iter().find(|sym| {
+                        // Skip already placed tiles
+                        !placed_tiles.contains(&t.id)
+                            && tile_borders.get(dir)
+                                == t.borders.get(sym).unwrap().get(&border_to_check)
+                    }).map(|sym| (t, sym))
+                })
+                .collect();
+            match matches.len() {
+                // No match for this, apparently it's an edge
+                0 => {}
+                // One match exactly, place it as specified
+                1 => {
+                    let (found, sym) = matches[0];
+                    let mut placing = found.clone();
+                    placing.rotate_to(sym);
+                    placed_tiles.push(placing.id);
+                    tilemap.insert(new_coords, placing);
+                    coords_to_check.push(new_coords);
+                }
+                _ => panic!(
+                    "Multiple matches for dir {:?} matches {:?} tile {}",
+                    dir, matches, cur_tile
+                ),
+            }
+        }
+    }
+
+    tilemap
+}
+
+fn log_tilemap(tilemap: &HashMap<(isize, isize), Tile>) {
+    // Find min/max X and Y in tilemap
+    let &min_x = tilemap.keys().map(|(x, _)| x).min().unwrap();
+    let &max_x = tilemap.keys().map(|(x, _)| x).max().unwrap();
+    let &min_y = tilemap.keys().map(|(_, y)| y).min().unwrap();
+    let &max_y = tilemap.keys().map(|(_, y)| y).max().unwrap();
+
+    debug!("x from {} to {}", min_x, max_x);
+    debug!("y from {} to {}", min_y, max_y);
+
+    debug!("IDs per X/Y:");
+    let mut header = String::from("    X ");
+    for x in min_x..=max_x {
+        header.push_str(&format!(" {:4}", x));
+    }
+    debug!("{}", header);
+    for y in min_y..=max_y {
+        let mut line = format!("Y {:3}:", y);
+        for x in min_x..=max_x {
+            match tilemap.get(&(x, y)) {
+                Some(tile) => line.push_str(&format!(" {:4}", tile.id)),
+                None => line.push_str("     "),
+            }
+        }
+        debug!("{}", line);
+    }
+}
+
+pub fn star_one(tiles: &[Tile]) -> Result<Int, Overflow> {
+    let tilemap = make_tilemap(tiles);
+    if log_enabled!(Level::Debug) {
+        log_tilemap(&tilemap);
+    }
+
+    // Find min/max X and Y in tilemap
+    // TODO: don't loop every time ?
+    let &min_x = tilemap.keys().map(|(x, _)| x).min().unwrap();
+    let &max_x = tilemap.keys().map(|(x, _)| x).max().unwrap();
+    let &min_y = tilemap.keys().map(|(_, y)| y).min().unwrap();
+    let &max_y = tilemap.keys().map(|(_, y)| y).max().unwrap();
+
+    let corners = [(min_x, min_y), (min_x, max_y), (max_x, min_y), (max_x, max_y)];
+    corners.iter().try_fold(Int::from(1), |result, corner| {
+        int::mul(&result, &int::from(tilemap.get(corner).unwrap().id)?)
+    })
+}
+
+/// Put the image together and turn it until the sea monsters show up,
+/// marked on the map.
+pub fn find_sea_monsters(tiles: &[Tile]) -> WorldMap {
+    let tilemap = make_tilemap(tiles);
+    let map = WorldMap::from_tilemap(&tilemap);
+
+    let mut orientations = vec![];
+    let mut next = map.clone();
+    for _ in 0..2 {
+        for _ in 0..4 {
+            orientations.push(next.clone());
+            next.rotate90cw();
+        }
+        next.flipns();
+    }
+
+    // The first orientation with monsters, also when all are searched in parallel
+    let with_monsters = |mut map: WorldMap| if map.find_monsters() { Some(map) } else { None };
+    #[cfg(not(feature = "parallel"))]
+    let found = orientations.into_iter().find_map(with_monsters);
+    #[cfg(feature = "parallel")]
+    let found = orientations.into_par_iter().find_map_first(with_monsters);
+    found.unwrap_or(map)
+}
+
+pub fn star_two(tiles: &[Tile]) -> usize {
+    find_sea_monsters(tiles).roughness()
+}
+
+static SEA_MONSTER: [&str; 3] = [
+    "..................#.",
+    "#....##....##....###",
+    ".#..#..#..#..#..#...",
+];
+
+/// Positions of all sea monsters in an image, facing the way they are drawn.
+fn sea_monsters(image: &Grid<bool>) -> Vec<(usize, usize)> {
+    let mut found = vec![];
+    for y in 0..=image.height() - SEA_MONSTER.len() {
+        for x in 0..=image.width() - SEA_MONSTER[0].len() {
+            let complete = SEA_MONSTER.iter().enumerate().all(|(dy, row)| {
+                row.chars().enumerate().all(|(dx, ch)| ch != '#' || image[(x + dx, y + dy)])
+            });
+            if complete {
+                found.push((x, y));
+            }
+        }
+    }
+    found
+}
+
+/// An image with sea monsters in it, that has no other sea monsters in any
+/// orientation. Returns the image and the number of monsters.
+fn image_with_sea_monsters(rng: &mut Random, size: usize) -> (Grid<bool>, usize) {
+    let (width, height) = (SEA_MONSTER[0].len(), SEA_MONSTER.len());
+    loop {
+        let mut image = Grid::from_fn(size, size, |_, _| rng.gen_bool(0.3));
+        // Monsters stay clear of the right and bottom edge, and of each other
+        let mut planted: Vec<(usize, usize)> = vec![];
+        for _ in 0..size * size / 100 + 1 {
+            let x = rng.gen_range(0..size - width);
+            let y = rng.gen_range(0..size - height);
+            let overlaps = planted
+                .iter()
+                .any(|&(px, py)| x < px + width && px < x + width && y < py + height && py < y + height);
+            if overlaps {
+                continue;
+            }
+            planted.push((x, y));
+            for (dy, row) in SEA_MONSTER.iter().enumerate() {
+                for (dx, ch) in row.chars().enumerate() {
+                    if ch == '#' {
+                        image[(x + dx, y + dy)] = true;
+                    }
+                }
+            }
+        }
+
+        planted.sort_unstable();
+        let mut found = sea_monsters(&image);
+        found.sort_unstable();
+        let mut turned = image.clone();
+        let mut elsewhere = false;
+        for i in 1..8 {
+            turned = if i == 4 { turned.rotate_cw().flip_vertical() } else { turned.rotate_cw() };
+            elsewhere |= !sea_monsters(&turned).is_empty();
+        }
+        if found == planted && !elsewhere {
+            return (image, planted.len());
+        }
+    }
+}
+
+/// A random border of 10 pixels between two given corners, that reads
+/// differently both ways and is not used yet in either direction.
+fn unique_border(rng: &mut Random, first: bool, last: bool, used: &mut HashSet<Vec<bool>>) -> Vec<bool> {
+    loop {
+        let mut border = vec![first];
+        border.extend((0..8).map(|_| rng.gen_bool(0.5)));
+        border.push(last);
+        let reversed: Vec<bool> = border.iter().rev().copied().collect();
+        if border != reversed && !used.contains(&border) && !used.contains(&reversed) {
+            used.insert(border.clone());
+            used.insert(reversed);
+            return border;
+        }
+    }
+}
+
+/// `size` by `size` tiles (between 3 and 10) cut from an image with sea
+/// monsters. Every border between two tiles is unique, so the tiles only fit
+/// together one way.
+pub fn generate(rng: &mut Random, size: usize) -> Generated {
+    let n = size.clamp(3, 10);
+    let (image, nr_monsters) = image_with_sea_monsters(rng, 8 * n);
+
+    // The borders meet at shared corners
+    let corners = Grid::from_fn(n + 1, n + 1, |_, _| rng.gen_bool(0.5));
+    let mut used = HashSet::new();
+    let horizontal = Grid::from_fn(n, n + 1, |x, y| {
+        unique_border(rng, corners[(x, y)], corners[(x + 1, y)], &mut used)
+    });
+    let vertical = Grid::from_fn(n + 1, n, |x, y| {
+        unique_border(rng, corners[(x, y)], corners[(x, y + 1)], &mut used)
+    });
+
+    let mut ids: Vec<usize> = (1000..10000).collect();
+    ids.shuffle(rng);
+    let ids = Grid::from_fn(n, n, |x, y| ids[y * n + x]);
+    let mut tiles = vec![];
+    for (tx, ty) in ids.positions() {
+        let mut tile = Grid::from_fn(10, 10, |x, y| match (x, y) {
+            (_, 0) => horizontal[(tx, ty)][x],
+            (_, 9) => horizontal[(tx, ty + 1)][x],
+            (0, _) => vertical[(tx, ty)][y],
+            (9, _) => vertical[(tx + 1, ty)][y],
+            _ => image[(8 * tx + x - 1, 8 * ty + y - 1)],
+        });
+        for _ in 0..rng.gen_range(0..4) {
+            tile = tile.rotate_cw();
+        }
+        if rng.gen_bool(0.5) {
+            tile = tile.flip_vertical();
+        }
+        let pixels = tile.render(|&on| if on { '#' } else { '.' });
+        tiles.push(format!("Tile {}:\n{}", ids[(tx, ty)], pixels.trim_end()));
+    }
+    tiles.shuffle(rng);
+
+    let corner_ids = ids[(0, 0)] * ids[(n - 1, 0)] * ids[(0, n - 1)] * ids[(n - 1, n - 1)];
+    let monster_pixels = SEA_MONSTER.iter().map(|row| row.matches('#').count()).sum::<usize>();
+    let roughness = image.iter().filter(|&&on| on).count() - nr_monsters * monster_pixels;
+    Generated {
+        input: tiles.join("\n\n"),
+        answer1: Some(corner_ids.to_string()),
+        answer2: Some(roughness.to_string()),
+    }
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u8 = 20;
+    type Puzzle = Vec<Tile>;
+
+    fn parse_lines(lines: impl Iterator<Item = String>) -> Result<Self::Puzzle, ParseError> {
+        parse_input(&mut lines.peekable())
+    }
+
+    fn part1(tiles: &Self::Puzzle) -> Option<String> {
+        int::answer(Self::DAY, star_one(tiles))
+    }
+
+    fn part2(tiles: &Self::Puzzle) -> Option<String> {
+        Some(star_two(tiles).to_string())
+    }
+
+    fn generate(rng: &mut Random, size: usize) -> Option<Generated> {
+        Some(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::{Direction, Symmetry, Tile};
+
+    static SYMMETRIES: [Symmetry; 8] = [
+        Symmetry::Identity0,
+        Symmetry::Rotate090,
+        Symmetry::Rotate180,
+        Symmetry::Rotate270,
+        Symmetry::FlipNSIdn,
+        Symmetry::FlipNS090,
+        Symmetry::FlipNS180,
+        Symmetry::FlipNS270,
+    ];
+
+    fn tile(pixels: &[bool]) -> Tile {
+        let mut lines = vec![String::from("Tile 1:")];
+        for row in pixels.chunks(10) {
+            lines.push(row.iter().map(|&on| if on { '#' } else { '.' }).collect());
+        }
+        Tile::from_lines_iter(&mut lines.iter().map(|x| x.as_str())).expect("Invalid tile")
+    }
+
+    static TEST_DATA: &str = "Tile 2311:
+..##.#..#.
+##..#.....
+#...##..#.
+####.#...#
+##.##.###.
+##...#.###
+.#.#.#..##
+..#....#..
+###...#.#.
+..###..###
+
+Tile 1951:
+#.##...##.
+#.####...#
+.....#..##
+#...######
+.##.#....#
+.###.#####
+###.##.##.
+.###....#.
+..#.#..#.#
+#...##.#..
+
+Tile 1171:
+####...##.
+#..##.#..#
+##.#..#.#.
+.###.####.
+..###.####
+.##....##.
+.#...####.
+#.##.####.
+####..#...
+.....##...
+
+Tile 1427:
+###.##.#..
+.#..#.##..
+.#.##.#..#
+#.#.#.##.#
+....#...##
+...##..##.
+...#.#####
+.#.####.#.
+..#..###.#
+..##.#..#.
+
+Tile 1489:
+##.#.#....
+..##...#..
+.##..##...
+..#...#...
+#####...#.
+#..#.#.#.#
+...#.#.#..
+##.#...##.
+..##.##.##
+###.##.#..
+
+Tile 2473:
+#....####.
+#..#.##...
+#.##..#...
+######.#.#
+.#...#.#.#
+.#########
+.###.#..#.
+########.#
+##...##.#.
+..###.#.#.
+
+Tile 2971:
+..#.#....#
+#...###...
+#.#.###...
+##.##..#..
+.#####..##
+.#..####.#
+#..#.#..#.
+..####.###
+..#.#.###.
+...#.#.#.#
+
+Tile 2729:
+...#.#.#.#
+####.#....
+..#.#.....
+....#..#.#
+.##..##.#.
+.#.####...
+####.#.#..
+##.####...
+##..#.##..
+#.##...##.
+
+Tile 3079:
+#.#.#####.
+.#..######
+..#.......
+######....
+####.#..#.
+.#...#.##.
+#.#####.##
+..#.###...
+..#.......
+..#.###...";
+
+    #[test]
+    fn test_star_one() {
+        let mut lines = TEST_DATA.lines().map(|x| x.to_string()).peekable();
+        let tiles = super::parse_input(&mut lines).expect("Invalid test data");
+        let ans = super::star_one(&tiles);
+        assert_eq!(ans, crate::int::from(20899048083289i64));
+    }
+
+    #[test]
+    fn test_star_two() {
+        let mut lines = TEST_DATA.lines().map(|x| x.to_string()).peekable();
+        let tiles = super::parse_input(&mut lines).expect("Invalid test data");
+        let ans = super::star_two(&tiles);
+        assert_eq!(ans, 273);
+    }
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..5 {
+            crate::generate::check::<super::Day20>(seed, 3);
+        }
+        let generated = crate::generate::check::<super::Day20>(0, 6);
+        assert_eq!(generated.input.matches("Tile").count(), 36);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_rotate_to_inverse(
+            pixels in prop::collection::vec(any::<bool>(), 100),
+            symmetry in prop::sample::select(&SYMMETRIES[..]),
+        ) {
+            let original = tile(&pixels);
+            let mut turned = original.clone();
+            turned.rotate_to(&symmetry);
+            prop_assert_eq!(turned.orientation, symmetry);
+
+            // Start again from the turned image and turn it back
+            let mut back = Tile { orientation: Symmetry::Identity0, ..turned };
+            back.rotate_to(&symmetry.inverse());
+            prop_assert_eq!(back.image, original.image);
+        }
+
+        #[test]
+        fn prop_rotate_to_borders(
+            pixels in prop::collection::vec(any::<bool>(), 100),
+            symmetry in prop::sample::select(&SYMMETRIES[..]),
+        ) {
+            let mut turned = tile(&pixels);
+            turned.rotate_to(&symmetry);
+            let borders = turned.calculate_borders().expect("Invalid tile");
+            for direction in [Direction::North, Direction::East, Direction::South, Direction::West].iter() {
+                prop_assert_eq!(borders[direction], turned.borders[&symmetry][direction]);
+            }
+        }
+
+        #[test]
+        fn prop_full_turns(pixels in prop::collection::vec(any::<bool>(), 100)) {
+            let original = tile(&pixels);
+            let mut turned = original.clone();
+            for _ in 0..4 {
+                turned.rotate90cw();
+            }
+            prop_assert_eq!(turned.orientation, Symmetry::Identity0);
+            turned.flipns();
+            turned.flipns();
+            prop_assert_eq!(turned.image, original.image);
+        }
+    }
+}
@@ -0,0 +1,345 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::vec::Vec;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::error::ParseError;
+use crate::generate::{self, Generated, Random};
+use crate::solution::Solution;
+
+#[derive(Debug)]
+pub struct Rule {
+    pub ingredients: Vec<String>,
+    pub allergens: HashSet<String>,
+}
+
+pub fn parse_rule(line: &str) -> Result<Rule, ParseError> {
+    let (ingredients, allergens) = line
+        .strip_suffix(')')
+        .and_then(|x| x.split_once(" (contains"))
+        .ok_or_else(|| ParseError::new(Day21::DAY, line, "missing '(contains ...)' allergen list"))?;
+    let rule = Rule {
+        ingredients: ingredients
+            .split(' ')
+            .filter(|i| !i.is_empty())
+            .map(|x| x.to_string())
+            .collect(),
+        allergens: allergens
+            .split(&[' ', ','][..])
+            .filter(|a| !a.is_empty())
+            .map(|x| x.to_string())
+            .collect(),
+    };
+    if rule.ingredients.is_empty() {
+        return Err(ParseError::new(Day21::DAY, line, "no ingredients"));
+    }
+
+    Ok(rule)
+}
+
+pub fn parse_rules(lines: impl Iterator<Item = String>) -> Result<Vec<Rule>, ParseError> {
+    let mut rules: Vec<Rule> = vec![];
+    for (i, line) in lines.enumerate() {
+        rules.push(parse_rule(&line).map_err(|e| e.at_line(i + 1))?);
+    }
+    if rules.is_empty() {
+        return Err(ParseError::new(Day21::DAY, "", "no foods in input"));
+    }
+
+    Ok(rules)
+}
+
+pub fn find_unique_allergens(rules: &[Rule]) -> HashSet<String> {
+    let mut unique_allergens: HashSet<String> = HashSet::new();
+    for allergen in rules[0].allergens.iter() {
+        unique_allergens.insert(allergen.clone());
+    }
+
+    for rule in rules {
+        for allergen in rule.allergens.iter() {
+            unique_allergens.insert(allergen.clone());
+        }
+    }
+
+    unique_allergens
+}
+
+pub fn find_unique_ingredients(rules: &[Rule]) -> HashSet<String> {
+    let mut unique_ingredients: HashSet<String> = HashSet::new();
+    for ingredient in rules[0].ingredients.iter() {
+        unique_ingredients.insert(ingredient.clone());
+    }
+
+    for rule in rules {
+        for ingredient in rule.ingredients.iter() {
+            unique_ingredients.insert(ingredient.clone());
+        }
+    }
+
+    unique_ingredients
+}
+
+pub fn find_inallergic_ingredients(rules: &[Rule]) -> HashSet<String> {
+    let unique_allergens = find_unique_allergens(rules);
+    let mut unique_ingredients = find_unique_ingredients(rules);
+
+    for allergen in unique_allergens.iter() {
+        // TODO: fix string allocations
+        let mut possible_ingredients: HashSet<String> = HashSet::new();
+        for rule in rules.iter() {
+            let mut ing_hash: HashSet<String> = HashSet::new();
+            for ing in rule.ingredients.iter() {
+                ing_hash.insert(ing.clone());
+            }
+            if rule.allergens.contains(&allergen.to_string()) {
+                if possible_ingredients.is_empty() {
+                    possible_ingredients = possible_ingredients.union(&ing_hash).map(|x| x.to_string()).collect();
+                } else {
+                    possible_ingredients = possible_ingredients.intersection(&ing_hash).map(|x| x.to_string()).collect();
+                }
+            }
+        }
+
+        unique_ingredients = unique_ingredients.difference(&possible_ingredients).map(|x| x.to_string()).collect();
+    }
+
+    unique_ingredients
+}
+
+pub fn star_one(rules: &[Rule]) -> usize {
+    let inallergic_ingredients = find_inallergic_ingredients(rules);
+
+    let mut result: usize = 0;
+    for ing in inallergic_ingredients {
+        for rule in rules.iter() {
+            result += rule.ingredients.iter().filter(|&i| i == &ing).count();
+        }
+    }
+
+    result
+}
+
+pub fn find_allergic_ingredients(rules: &[Rule]) -> HashMap<String, String> {
+    // Yes this is inefficient
+    let unique_allergens = find_unique_allergens(rules);
+    let inallergics = find_inallergic_ingredients(rules);
+    let mut allergic_ingredients: HashMap<String, String> = HashMap::new();
+    let mut possible_ingredients_per_allergen: HashMap<String, HashSet<String>> = HashMap::new();
+
+    for allergen in unique_allergens.iter() {
+        // TODO: fix string allocations
+        let mut possible_ingredients: HashSet<String> = HashSet::new();
+        for rule in rules.iter() {
+            let mut ing_hash: HashSet<String> = HashSet::new();
+            for ing in rule.ingredients.iter() {
+                ing_hash.insert(ing.clone());
+            }
+            if rule.allergens.contains(&allergen.to_string()) {
+                if possible_ingredients.is_empty() {
+                    possible_ingredients = possible_ingredients.union(&ing_hash).map(|x| x.to_string()).collect();
+                } else {
+                    possible_ingredients = possible_ingredients.intersection(&ing_hash).map(|x| x.to_string()).collect();
+                }
+            }
+        }
+
+        possible_ingredients = possible_ingredients.difference(&inallergics).map(|x| x.to_string()).collect();
+        *possible_ingredients_per_allergen.entry(allergen.to_string()).or_default() = possible_ingredients;
+    }
+
+    // Now remove the known allergic ingredients as well from the possibilities
+    while allergic_ingredients.len() != unique_allergens.len() {
+        for allergen in unique_allergens.iter() {
+            // Remove all the allergens with one possibility
+            let set = possible_ingredients_per_allergen.get(allergen).unwrap();
+            if set.len() != 1 {
+                continue;
+            }
+
+            let ing = set.iter().next().unwrap().clone();
+            // Remove the ingredient from all the other possibilities
+            for (_, v) in possible_ingredients_per_allergen.iter_mut() {
+                v.remove(&ing);
+            }
+            *allergic_ingredients.entry(allergen.to_string()).or_default() = ing;
+        }
+    }
+
+    allergic_ingredients
+}
+
+pub fn star_two(rules: &[Rule]) -> String {
+    let allergic_ingredients = find_allergic_ingredients(rules);
+    let mut sorted_allergens: Vec<String> = allergic_ingredients.keys().map(|k| k.to_string()).collect();
+    sorted_allergens.sort();
+    let mut result: Vec<String> = vec![];
+    for allergen in sorted_allergens.iter() {
+        result.push(allergic_ingredients.get(allergen).unwrap().to_string());
+    }
+
+    result.join(",")
+}
+
+static ALLERGENS: [&str; 10] = [
+    "dairy", "eggs", "fish", "gluten", "nuts", "peanuts", "sesame", "shellfish", "soy", "wheat",
+];
+
+/// `size` foods, with an allergen for every four foods. Every allergen is in
+/// one ingredient, which is in all foods listing the allergen; the foods are
+/// picked again until elimination pins down every allergen.
+pub fn generate(rng: &mut Random, size: usize) -> Generated {
+    let nr_foods = size.max(1);
+    let nr_allergens = (nr_foods / 4).clamp(1, ALLERGENS.len());
+    let mut allergens: Vec<&str> = ALLERGENS.to_vec();
+    allergens.shuffle(rng);
+    allergens.truncate(nr_allergens);
+
+    let mut names = HashSet::new();
+    while names.len() < 4 * nr_allergens + 6 {
+        names.insert(generate::word(rng, 4, 8));
+    }
+    let mut ingredients: Vec<String> = names.into_iter().collect();
+    ingredients.sort();
+    ingredients.shuffle(rng);
+    // The ingredient with allergen `i` is ingredient `i`
+    let dangerous = &ingredients[..nr_allergens];
+
+    loop {
+        let mut foods: Vec<(Vec<&String>, Vec<&str>)> = vec![];
+        for i in 0..nr_foods {
+            let mut listed: Vec<usize> = (0..nr_allergens).filter(|_| rng.gen_bool(0.3)).collect();
+            if listed.is_empty() || i < nr_allergens {
+                listed.push(i % nr_allergens);
+            }
+            listed.sort_unstable();
+            listed.dedup();
+
+            // Allergens are not always listed, so the other ingredients can
+            // be anything
+            let mut food: Vec<&String> = listed.iter().map(|&a| &dangerous[a]).collect();
+            for ingredient in ingredients.iter() {
+                if !food.contains(&ingredient) && rng.gen_bool(0.4) {
+                    food.push(ingredient);
+                }
+            }
+            food.shuffle(rng);
+            foods.push((food, listed.iter().map(|&a| allergens[a]).collect()));
+        }
+
+        // Narrow down the ingredients every allergen can be in, as the
+        // solver should
+        let mut candidates: Vec<HashSet<&String>> = (0..nr_allergens)
+            .map(|a| {
+                let mut with_allergen = foods.iter().filter(|(_, listed)| listed.contains(&allergens[a]));
+                let first: HashSet<&String> = with_allergen.next().unwrap().0.iter().copied().collect();
+                with_allergen.fold(first, |set, (food, _)| {
+                    set.into_iter().filter(|ingredient| food.contains(ingredient)).collect()
+                })
+            })
+            .collect();
+        let possibly_dangerous: HashSet<&String> = candidates.iter().flatten().copied().collect();
+        let mut known = 0;
+        while let Some(a) = candidates.iter().position(|set| set.len() == 1) {
+            let ingredient = *candidates[a].iter().next().unwrap();
+            for set in candidates.iter_mut() {
+                set.remove(ingredient);
+            }
+            known += 1;
+        }
+        if known < nr_allergens {
+            continue;
+        }
+
+        let safe = foods
+            .iter()
+            .map(|(food, _)| food.iter().filter(|ingredient| !possibly_dangerous.contains(*ingredient)).count())
+            .sum::<usize>();
+        let mut canonical: Vec<(&str, &String)> = allergens.iter().copied().zip(dangerous.iter()).collect();
+        canonical.sort();
+        let canonical: Vec<&str> = canonical.iter().map(|(_, ingredient)| ingredient.as_str()).collect();
+
+        let lines: Vec<String> = foods
+            .iter()
+            .map(|(food, listed)| {
+                let food: Vec<&str> = food.iter().map(|x| x.as_str()).collect();
+                format!("{} (contains {})", food.join(" "), listed.join(", "))
+            })
+            .collect();
+        return Generated {
+            input: lines.join("\n"),
+            answer1: Some(safe.to_string()),
+            answer2: Some(canonical.join(",")),
+        };
+    }
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u8 = 21;
+    type Puzzle = Vec<Rule>;
+
+    fn parse_lines(lines: impl Iterator<Item = String>) -> Result<Self::Puzzle, ParseError> {
+        parse_rules(lines)
+    }
+
+    fn part1(rules: &Self::Puzzle) -> Option<String> {
+        Some(star_one(rules).to_string())
+    }
+
+    fn part2(rules: &Self::Puzzle) -> Option<String> {
+        Some(star_two(rules))
+    }
+
+    fn generate(rng: &mut Random, size: usize) -> Option<Generated> {
+        Some(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    static TEST_DATA: &str = "mxmxvkd kfcds sqjhc nhms (contains dairy, fish)
+trh fvjkl sbzzf mxmxvkd (contains dairy)
+sqjhc fvjkl (contains soy)
+sqjhc mxmxvkd sbzzf (contains fish)
+";
+
+    #[test]
+    fn test_star_one() {
+        let rules: Vec<super::Rule> =
+            super::parse_rules(TEST_DATA.lines().map(|x| x.to_string()))
+                .expect("Invalid test data");
+
+        let ans = super::star_one(&rules);
+        assert_eq!(ans, 5);
+    }
+
+    #[test]
+    fn test_star_two() {
+        let rules: Vec<super::Rule> =
+            super::parse_rules(TEST_DATA.lines().map(|x| x.to_string()))
+                .expect("Invalid test data");
+
+        let ans = super::star_two(&rules);
+        assert_eq!(ans, "mxmxvkd,sqjhc,fvjkl");
+    }
+
+    #[test]
+    fn test_parse_error() {
+        use crate::solution::Solution;
+
+        let err = super::Day21::parse("mxmxvkd kfcds (contains dairy)\nsqjhc fvjkl\n")
+            .expect_err("Missing allergen list accepted");
+        assert_eq!(err.line, Some(2));
+        assert_eq!(err.text, "sqjhc fvjkl");
+    }
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..5 {
+            crate::generate::check::<super::Day21>(seed, 40);
+        }
+        crate::generate::check::<super::Day21>(0, 1);
+    }
+}
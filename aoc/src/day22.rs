@@ -0,0 +1,315 @@
+use std::collections::{HashSet, VecDeque};
+use std::vec::Vec;
+use log::{debug, trace};
+use rand::seq::SliceRandom;
+
+use crate::error::ParseError;
+use crate::generate::{Generated, Random};
+use crate::solution::Solution;
+
+pub fn parse_lines(lines: impl Iterator<Item = String>) -> Result<(Vec<usize>, Vec<usize>), ParseError> {
+    let mut deck_1: Vec<usize> = vec![];
+    let mut deck_2: Vec<usize> = vec![];
+    let mut cur_deck = &mut deck_1;
+
+    for (i, line) in lines.enumerate() {
+        if line.starts_with("Player 1") {
+            continue;
+        }
+        if line.starts_with("Player 2") {
+            cur_deck = &mut deck_2;
+            continue;
+        }
+        if line.is_empty() {
+            continue;
+        }
+
+        let val = line
+            .parse::<usize>()
+            .map_err(|_| ParseError::new(Day22::DAY, &line, "invalid card").at_line(i + 1))?;
+        cur_deck.push(val);
+    }
+
+    Ok((deck_1, deck_2))
+}
+
+pub fn star_one(deck1: &[usize], deck2: &[usize]) -> usize {
+    let mut p1deck = deck1.to_vec();
+    let mut p2deck = deck2.to_vec();
+
+    while !p1deck.is_empty() && !p2deck.is_empty() {
+        let p1draw = p1deck.remove(0);
+        let p2draw = p2deck.remove(0);
+
+        if p1draw > p2draw {
+            p1deck.push(p1draw);
+            p1deck.push(p2draw);
+        } else if p1draw == p2draw {
+            panic!();
+        } else {
+            p2deck.push(p2draw);
+            p2deck.push(p1draw);
+        }
+    }
+
+    let mut winner_deck = &p1deck;
+    if p1deck.is_empty() {
+        winner_deck = &p2deck;
+    }
+
+    let mut result = 0;
+    for (idx, val) in winner_deck.iter().rev().enumerate() {
+        result += (idx + 1) * val;
+    }
+
+    result
+}
+
+pub fn play_game(game_nr: &mut usize, deck1: &[usize], deck2: &[usize]) -> (usize, usize) {
+    let mut p1deck = deck1.to_vec();
+    let mut p2deck = deck2.to_vec();
+    let mut prev_rounds: Vec<(Vec<usize>, Vec<usize>)> = vec![];
+    let this_game_nr = *game_nr;
+    let mut round_nr = 1;
+
+    debug!("=== Game {} ===", this_game_nr);
+
+    while !p1deck.is_empty() && !p2deck.is_empty() {
+        trace!("-- Round {} (Game {}) --", round_nr, this_game_nr);
+        trace!("Player 1's deck: {:?}", p1deck);
+        trace!("Player 2's deck: {:?}", p2deck);
+
+        // Check on previous rounds
+        for round in prev_rounds.iter() {
+            if round.0 == p1deck && round.1 == p2deck {
+                debug!("Infinite recursion detected, P1 wins by default");
+                let mut result = 0;
+                for (idx, val) in p1deck.iter().rev().enumerate() {
+                    result += (idx + 1) * val;
+                }
+                return (1, result);
+            }
+        }
+
+        // Add current round to previous
+        prev_rounds.push((p1deck.clone(), p2deck.clone()));
+
+        let p1draw = p1deck.remove(0);
+        let p2draw = p2deck.remove(0);
+
+        trace!("Player 1 plays: {}", p1draw);
+        trace!("Player 2 plays: {}", p2draw);
+
+        if p1deck.len() >= p1draw && p2deck.len() >= p2draw {
+            trace!("Playing a sub-game to determine the winner...");
+
+            // Play sub game with only the # cards as determined by the draw
+            let subdeck1 = p1deck[..p1draw].to_vec();
+            let subdeck2 = p2deck[..p2draw].to_vec();
+            *game_nr += 1;
+            
+            let (winner, _) = play_game(game_nr, &subdeck1, &subdeck2);
+            trace!("...anyway, back to game {}.", this_game_nr);
+            if winner == 1 {
+                trace!("Player 1 wins round {} of game {}!", round_nr, this_game_nr);
+                p1deck.push(p1draw);
+                p1deck.push(p2draw);
+            } else {
+                trace!("Player 2 wins round {} of game {}!", round_nr, this_game_nr);
+                p2deck.push(p2draw);
+                p2deck.push(p1draw);
+            }
+        } else if p1draw > p2draw {
+            trace!("Player 1 wins round {} of game {}!", round_nr, this_game_nr);
+            p1deck.push(p1draw);
+            p1deck.push(p2draw);
+        } else if p1draw == p2draw {
+            panic!();
+        } else {
+            trace!("Player 2 wins round {} of game {}!", round_nr, this_game_nr);
+            p2deck.push(p2draw);
+            p2deck.push(p1draw);
+        }
+
+        round_nr += 1;
+    }
+
+    if this_game_nr == 1 {
+        debug!("== Post-game results ==");
+        debug!("Player 1's deck: {:?}", p1deck);
+        debug!("Player 2's deck: {:?}", p2deck);
+    }
+
+    let mut winner: usize = 1;
+    let mut winner_deck = &p1deck;
+    if p1deck.is_empty() {
+        winner = 2;
+        winner_deck = &p2deck;
+    }
+
+    if this_game_nr > 1 {
+        debug!("The winner of game {} is player {}!", this_game_nr, winner);
+    }
+
+    let mut result = 0;
+    for (idx, val) in winner_deck.iter().rev().enumerate() {
+        result += (idx + 1) * val;
+    }
+
+    (winner, result)
+}
+
+pub fn star_two(deck1: &[usize], deck2: &[usize]) -> usize {
+    let mut game_nr = 1;
+    play_game(&mut game_nr, deck1, deck2).1
+}
+
+fn score(deck: &VecDeque<usize>) -> usize {
+    deck.iter().rev().enumerate().map(|(i, card)| (i + 1) * card).sum()
+}
+
+/// Play a game, recursive or not, giving whether player 1 wins and the score
+/// of the winner. `None` when plain combat goes on forever.
+fn play(mut deck1: VecDeque<usize>, mut deck2: VecDeque<usize>, recursive: bool) -> Option<(bool, usize)> {
+    let mut seen = HashSet::new();
+    while !deck1.is_empty() && !deck2.is_empty() {
+        if !seen.insert((deck1.clone(), deck2.clone())) {
+            if recursive {
+                return Some((true, score(&deck1)));
+            }
+            return None;
+        }
+
+        let (card1, card2) = (deck1.pop_front().unwrap(), deck2.pop_front().unwrap());
+        let player1_wins = if recursive && deck1.len() >= card1 && deck2.len() >= card2 {
+            let sub1 = deck1.iter().take(card1).copied().collect();
+            let sub2 = deck2.iter().take(card2).copied().collect();
+            play(sub1, sub2, true)?.0
+        } else {
+            card1 > card2
+        };
+        if player1_wins {
+            deck1.extend([card1, card2].iter());
+        } else {
+            deck2.extend([card2, card1].iter());
+        }
+    }
+
+    if deck2.is_empty() {
+        Some((true, score(&deck1)))
+    } else {
+        Some((false, score(&deck2)))
+    }
+}
+
+/// The cards 1 to `2 * size` dealt over two players, played out both ways.
+/// Deals where plain combat never ends are dealt again. At most 25 cards per
+/// player as in the puzzle, as recursive combat with bigger decks can take
+/// minutes.
+pub fn generate(rng: &mut Random, size: usize) -> Generated {
+    let size = size.clamp(1, 25);
+    let mut cards: Vec<usize> = (1..=2 * size).collect();
+    loop {
+        cards.shuffle(rng);
+        let (deck1, deck2): (VecDeque<usize>, VecDeque<usize>) = (
+            cards[..size].iter().copied().collect(),
+            cards[size..].iter().copied().collect(),
+        );
+        let combat = match play(deck1.clone(), deck2.clone(), false) {
+            Some((_, score)) => score,
+            None => continue,
+        };
+        let (_, recursive_combat) = play(deck1, deck2, true).unwrap();
+
+        let deck = |cards: &[usize]| cards.iter().map(|x| x.to_string()).collect::<Vec<String>>().join("\n");
+        return Generated {
+            input: format!("Player 1:\n{}\n\nPlayer 2:\n{}", deck(&cards[..size]), deck(&cards[size..])),
+            answer1: Some(combat.to_string()),
+            answer2: Some(recursive_combat.to_string()),
+        };
+    }
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    const DAY: u8 = 22;
+    type Puzzle = (Vec<usize>, Vec<usize>);
+
+    fn parse_lines(lines: impl Iterator<Item = String>) -> Result<Self::Puzzle, ParseError> {
+        parse_lines(lines)
+    }
+
+    fn part1((deck1, deck2): &Self::Puzzle) -> Option<String> {
+        Some(star_one(deck1, deck2).to_string())
+    }
+
+    fn part2((deck1, deck2): &Self::Puzzle) -> Option<String> {
+        Some(star_two(deck1, deck2).to_string())
+    }
+
+    fn generate(rng: &mut Random, size: usize) -> Option<Generated> {
+        Some(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    static TEST_DATA: &str = "Player 1:
+9
+2
+6
+3
+1
+
+Player 2:
+5
+8
+4
+7
+10";
+
+    #[test]
+    fn test_star_one() {
+        let lines = TEST_DATA.lines().map(String::from);
+        let (deck1, deck2) = super::parse_lines(lines).expect("Invalid test data");
+
+        let ans = super::star_one(&deck1, &deck2);
+        assert_eq!(ans, 306);
+    }
+
+    static TEST_DATA_INF: &str = "Player 1:
+43
+19
+
+Player 2:
+2
+29
+14";
+
+    #[test]
+    fn test_star_two() {
+        let lines = TEST_DATA.lines().map(String::from);
+        let (deck1, deck2) = super::parse_lines(lines).expect("Invalid test data");
+
+        let ans = super::star_two(&deck1, &deck2);
+        assert_eq!(ans, 291);
+
+        let lines = TEST_DATA_INF.lines().map(String::from);
+        let (deck1, deck2) = super::parse_lines(lines).expect("Invalid test data");
+
+        let ans = super::star_two(&deck1, &deck2);
+        assert_eq!(ans, 105);
+    }
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..5 {
+            crate::generate::check::<super::Day22>(seed, 10);
+        }
+        crate::generate::check::<super::Day22>(0, 1);
+
+        let generated = super::generate(&mut crate::generate::random(2), 40);
+        assert_eq!(generated.input.lines().count(), 2 * 25 + 3);
+    }
+}
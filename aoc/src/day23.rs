@@ -0,0 +1,199 @@
+use std::vec::Vec;
+use log::{log_enabled, trace, Level};
+use rand::seq::SliceRandom;
+
+use crate::error::ParseError;
+use crate::generate::{Generated, Random};
+use crate::solution::Solution;
+
+fn dump_state(cups: &[usize], move_nr: usize, cur_cup: usize) {
+    let cups: Vec<String> = cups
+        .iter()
+        .enumerate()
+        .map(|(i, c)| if i == cur_cup { format!("({})", c) } else { c.to_string() })
+        .collect();
+    trace!("-- move {} --", move_nr);
+    trace!("cups: {}", cups.join(" "));
+}
+
+fn dump_pickup(pickup: &[usize]) {
+    let pickup: Vec<String> = pickup.iter().map(|p| p.to_string()).collect();
+    trace!("pick up: {}", pickup.join(", "));
+}
+
+pub fn play_game(cups: &mut Vec<usize>, nr_moves: usize) {
+    let mut cur_cup: usize = 0;
+    for move_nr in 1..=nr_moves {
+        if log_enabled!(Level::Trace) {
+            dump_state(cups, move_nr, cur_cup);
+        }
+
+        // Pick up the next three cups
+        let cur_label = cups[cur_cup];
+        let mut picked_up: Vec<usize> = vec![];
+        for _ in 0..3 {
+            let pickup_idx = (cups.iter().position(|&x| x == cur_label).unwrap() + 1) % cups.len();
+            picked_up.push(cups.remove(pickup_idx));
+        }
+        if log_enabled!(Level::Trace) {
+            dump_pickup(&picked_up);
+        }
+
+        // Select destination cup
+        let mut dest_label = cur_label - 1;
+        while picked_up.contains(&dest_label) {
+            dest_label -= 1;
+        }
+
+        if dest_label < *cups.iter().min().unwrap() {
+            dest_label = *cups.iter().chain(picked_up.iter()).max().unwrap();
+            while picked_up.contains(&dest_label) {
+                dest_label -= 1;
+            }
+        }
+        trace!("destination: {}", dest_label);
+
+        // Insert cups after destination cup
+        let dest_index = (cups.iter().position(|&x| x == dest_label).unwrap() + 1) % cups.len();
+        for p in picked_up.iter().rev() {
+            cups.insert(dest_index, *p);
+        }
+
+        cur_cup = (cups.iter().position(|&x| x == cur_label).unwrap() + 1) % cups.len();
+    }
+
+    trace!("-- final --");
+    trace!("cups: {:?}", cups);
+}
+
+pub fn score(cups: &[usize]) -> usize {
+    let first_index = cups.iter().position(|&x| x == 1).unwrap();
+    let mut result: usize = 0;
+    let max: u32 = cups.len() as u32;
+    for i in 1..max {
+        let label = cups[(first_index + i as usize) % cups.len()];
+        result += label * 10usize.pow(max-i-1);
+    }
+
+    result
+}
+
+pub fn star_one(cups: &[usize]) -> usize {
+    let mut gamecups = cups.to_vec();
+    play_game(&mut gamecups, 100);
+    score(&gamecups)
+}
+
+/// A circle of `size` cups (between 5 and 9) in random order. Part two is
+/// not solved yet, so only the order after 100 moves is known.
+pub fn generate(rng: &mut Random, size: usize) -> Generated {
+    let nr_cups = size.clamp(5, 9);
+    let mut cups: Vec<usize> = (1..=nr_cups).collect();
+    cups.shuffle(rng);
+
+    // The cup clockwise of every cup
+    let mut next = vec![0; nr_cups + 1];
+    for (i, &cup) in cups.iter().enumerate() {
+        next[cup] = cups[(i + 1) % nr_cups];
+    }
+    let mut current = cups[0];
+    for _ in 0..100 {
+        let picked = [next[current], next[next[current]], next[next[next[current]]]];
+        next[current] = next[picked[2]];
+        let mut destination = current;
+        loop {
+            destination = if destination == 1 { nr_cups } else { destination - 1 };
+            if !picked.contains(&destination) {
+                break;
+            }
+        }
+        next[picked[2]] = next[destination];
+        next[destination] = picked[0];
+        current = next[current];
+    }
+
+    let mut labels = String::new();
+    let mut cup = next[1];
+    while cup != 1 {
+        labels.push_str(&cup.to_string());
+        cup = next[cup];
+    }
+    Generated {
+        input: cups.iter().map(|x| x.to_string()).collect(),
+        answer1: Some(labels),
+        answer2: None,
+    }
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: u8 = 23;
+    type Puzzle = Vec<usize>;
+
+    fn parse_lines(mut lines: impl Iterator<Item = String>) -> Result<Self::Puzzle, ParseError> {
+        // Input provided as single string
+        let line = lines.next().unwrap_or_default();
+        let line = line.trim();
+        let cups = line
+            .chars()
+            .enumerate()
+            .map(|(i, ch)| {
+                ch.to_digit(10).map(|x| x as usize).ok_or_else(|| {
+                    ParseError::new(Self::DAY, &ch.to_string(), "cup label should be a digit")
+                        .at_line(1)
+                        .at_column(i + 1)
+                })
+            })
+            .collect::<Result<Vec<usize>, ParseError>>()?;
+
+        // The game needs every label from 1 up to the number of cups, and enough cups to pick up three
+        let mut sorted = cups.clone();
+        sorted.sort_unstable();
+        if cups.len() < 5 || sorted.iter().enumerate().any(|(i, &x)| x != i + 1) {
+            return Err(ParseError::new(Self::DAY, line, "cups should be labeled 1 up to the number of cups (at least 5)").at_line(1));
+        }
+
+        Ok(cups)
+    }
+
+    fn part1(cups: &Self::Puzzle) -> Option<String> {
+        Some(star_one(cups).to_string())
+    }
+
+    fn part2(_cups: &Self::Puzzle) -> Option<String> {
+        // Not solved yet
+        None
+    }
+
+    fn generate(rng: &mut Random, size: usize) -> Option<Generated> {
+        Some(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_star_one() {
+        // Input provided as single string
+        let test_cups: Vec<usize> = vec![3, 8, 9, 1, 2, 5, 4, 6, 7];
+
+        let mut cups = test_cups.clone();
+        super::play_game(&mut cups, 10);
+        let ans = super::score(&cups);
+        assert_eq!(ans, 92658374);
+
+        let mut cups = test_cups.clone();
+        super::play_game(&mut cups, 100);
+        let ans = super::score(&cups);
+        assert_eq!(ans, 67384529);
+    }
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..5 {
+            crate::generate::check::<super::Day23>(seed, 9);
+        }
+        crate::generate::check::<super::Day23>(0, 1);
+    }
+}
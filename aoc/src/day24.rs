@@ -0,0 +1,322 @@
+use std::collections::HashMap;
+use std::vec::Vec;
+use log::{debug, log_enabled, trace, Level};
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::automaton::{Neighbourhood, Rule, Sparse};
+use crate::error::ParseError;
+use crate::generate::{Generated, Random};
+use crate::grid::Grid;
+use crate::solution::Solution;
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum TileColor {
+    Black,
+    White,
+}
+
+/// Steps to the six neighbours of a hexagonal tile; going north east or
+/// south west also shifts the x coordinate.
+const NEIGHBOURS: [(isize, isize); 6] = [(0, -1), (1, -1), (1, 0), (0, 1), (-1, 1), (-1, 0)];
+
+/// The part of the endless floor with every tile flipped by the directions.
+#[derive(Debug, Clone)]
+pub struct Floor {
+    pub tiles: Grid<TileColor>,
+    // Position of the reference tile in the grid
+    pub origin: (isize, isize),
+}
+
+impl Floor {
+    pub fn color(&self, pos: (isize, isize)) -> TileColor {
+        self.tiles
+            .get(pos.0 + self.origin.0, pos.1 + self.origin.1)
+            .copied()
+            .unwrap_or(TileColor::White)
+    }
+
+    pub fn nr_black(&self) -> usize {
+        self.tiles.iter().filter(|&&col| col == TileColor::Black).count()
+    }
+}
+
+/// Show the black tiles, given relative to the reference tile.
+fn log_floor(black: &[(isize, isize)]) {
+    if black.is_empty() {
+        trace!("Floor is all white");
+        return;
+    }
+    let min_x = black.iter().map(|pos| pos.0).min().unwrap();
+    let max_x = black.iter().map(|pos| pos.0).max().unwrap();
+    let min_y = black.iter().map(|pos| pos.1).min().unwrap();
+    let max_y = black.iter().map(|pos| pos.1).max().unwrap();
+
+    trace!("Floor from Y {}-{} X {}-{}", min_y, max_y, min_x, max_x);
+    let mut header = String::from("        ");
+    for x in min_x..=max_x {
+        header.push_str(&format!("{:3} ", x));
+    }
+    trace!("{}", header);
+    for y in min_y..=max_y {
+        let mut line = format!("Y: {:3} ", y);
+        if y.abs() % 2 == 1 {
+            line.push(' ');
+        }
+        for x in min_x..=max_x {
+            line.push_str(if black.contains(&(x, y)) { " ##" } else { " .." });
+        }
+        trace!("{}", line);
+    }
+}
+
+/// Follow the directions on a line from the reference tile, giving the
+/// position of the tile to flip.
+pub fn parse_tile(line: &str) -> Result<(isize, isize), ParseError> {
+    let invalid = |i: usize| ParseError::new(Day24::DAY, line, "invalid direction").at_column(i + 1);
+
+    let mut cur_pos: (isize, isize) = (0, 0);
+    let mut diriter = line.chars().enumerate();
+    loop {
+        match diriter.next() {
+            Some((_, 'e')) => cur_pos = (cur_pos.0 + 1, cur_pos.1),
+            Some((_, 'w')) => cur_pos = (cur_pos.0 - 1, cur_pos.1),
+            Some((i, 'n')) => match diriter.next() {
+                Some((_, 'e')) => cur_pos = (cur_pos.0 + 1, cur_pos.1 - 1),
+                Some((_, 'w')) => cur_pos = (cur_pos.0, cur_pos.1 - 1),
+                None | Some(_) => return Err(invalid(i)),
+            },
+            Some((i, 's')) => match diriter.next() {
+                Some((_, 'e')) => cur_pos = (cur_pos.0, cur_pos.1 + 1),
+                Some((_, 'w')) => cur_pos = (cur_pos.0 - 1, cur_pos.1 + 1),
+                None | Some(_) => return Err(invalid(i)),
+            },
+            None => return Ok(cur_pos),
+            Some((i, _)) => return Err(invalid(i)),
+        }
+    }
+}
+
+pub fn parse_tiles(lines: impl Iterator<Item = String>) -> Result<Vec<(isize, isize)>, ParseError> {
+    let tiles = lines
+        .enumerate()
+        .map(|(i, line)| parse_tile(&line).map_err(|e| e.at_line(i + 1)))
+        .collect::<Result<Vec<(isize, isize)>, ParseError>>()?;
+    if tiles.is_empty() {
+        return Err(ParseError::new(Day24::DAY, "", "no tiles to flip"));
+    }
+
+    Ok(tiles)
+}
+
+/// Flip the tiles, leaving `margin` white tiles around them.
+pub fn make_floor(tiles: &[(isize, isize)], margin: isize) -> Floor {
+    let min_x = tiles.iter().map(|pos| pos.0).min().unwrap_or(0) - margin;
+    let max_x = tiles.iter().map(|pos| pos.0).max().unwrap_or(0) + margin;
+    let min_y = tiles.iter().map(|pos| pos.1).min().unwrap_or(0) - margin;
+    let max_y = tiles.iter().map(|pos| pos.1).max().unwrap_or(0) + margin;
+    let width = (max_x - min_x + 1) as usize;
+    let height = (max_y - min_y + 1) as usize;
+    let mut floor = Floor {
+        tiles: Grid::new(width, height, TileColor::White),
+        origin: (-min_x, -min_y),
+    };
+
+    for cur_pos in tiles.iter() {
+        let tile = &mut floor.tiles[((cur_pos.0 - min_x) as usize, (cur_pos.1 - min_y) as usize)];
+        *tile = if *tile == TileColor::White {
+            TileColor::Black
+        } else {
+            TileColor::White
+        };
+    }
+
+    floor
+}
+
+/// The black tiles of a floor, relative to the reference tile.
+fn black_tiles(floor: &Floor) -> Vec<(isize, isize)> {
+    let (ox, oy) = floor.origin;
+    floor
+        .tiles
+        .positions()
+        .filter(|&pos| floor.tiles[pos] == TileColor::Black)
+        .map(|(x, y)| (x as isize - ox, y as isize - oy))
+        .collect()
+}
+
+/// Flip the tiles, then live the given number of days in which all tiles
+/// flip at once according to their number of black neighbours. Gives the
+/// number of black tiles at the end.
+pub fn black_after_days(tiles: &[(isize, isize)], nr_days: usize) -> usize {
+    let floor = make_floor(tiles, 0);
+    let black = black_tiles(&floor).into_iter().map(|(x, y)| [x, y]);
+    let neighbourhood = Neighbourhood::Offsets(NEIGHBOURS.iter().map(|&(dx, dy)| [dx, dy]).collect());
+    let mut exhibit = Sparse::new(black, &neighbourhood, Rule::new(&[2], &[1, 2]));
+
+    for day in 1..=nr_days {
+        exhibit.step();
+        debug!("Day {}: {}", day, exhibit.nr_alive());
+        if log_enabled!(Level::Trace) {
+            let black: Vec<(isize, isize)> = exhibit.alive().map(|&[x, y]| (x, y)).collect();
+            log_floor(&black);
+        }
+    }
+
+    exhibit.nr_alive()
+}
+
+pub fn star_one(tiles: &[(isize, isize)]) -> isize {
+    let floor = make_floor(tiles, 0);
+    if log_enabled!(Level::Trace) {
+        log_floor(&black_tiles(&floor));
+    }
+
+    floor.nr_black() as isize
+}
+
+pub fn star_two(tiles: &[(isize, isize)]) -> isize {
+    black_after_days(tiles, 100) as isize
+}
+
+/// The steps in cube coordinates, which add up to zero for every tile.
+const STEPS: [(&str, (isize, isize, isize)); 6] = [
+    ("e", (1, -1, 0)),
+    ("se", (0, -1, 1)),
+    ("sw", (-1, 0, 1)),
+    ("w", (-1, 1, 0)),
+    ("nw", (0, 1, -1)),
+    ("ne", (1, 0, -1)),
+];
+
+/// `size` lines of directions. Some lines take the steps of an earlier line
+/// in another order, flipping the same tile back. Only the tiles flipped at
+/// the start are known; the exhibit has to be lived through.
+pub fn generate(rng: &mut Random, size: usize) -> Generated {
+    let mut walks: Vec<Vec<usize>> = vec![];
+    for _ in 0..size.max(1) {
+        let walk = if !walks.is_empty() && rng.gen_bool(0.2) {
+            let mut walk = walks.choose(rng).unwrap().clone();
+            walk.shuffle(rng);
+            walk
+        } else {
+            let len = rng.gen_range(5..=20);
+            (0..len).map(|_| rng.gen_range(0..STEPS.len())).collect()
+        };
+        walks.push(walk);
+    }
+
+    let mut flips: HashMap<(isize, isize, isize), usize> = HashMap::new();
+    for walk in walks.iter() {
+        let tile = walk.iter().fold((0, 0, 0), |(x, y, z), &step| {
+            let (dx, dy, dz) = STEPS[step].1;
+            (x + dx, y + dy, z + dz)
+        });
+        *flips.entry(tile).or_insert(0) += 1;
+    }
+    let black = flips.values().filter(|&&count| count % 2 == 1).count();
+
+    let lines: Vec<String> = walks
+        .iter()
+        .map(|walk| walk.iter().map(|&step| STEPS[step].0).collect())
+        .collect();
+    Generated {
+        input: lines.join("\n"),
+        answer1: Some(black.to_string()),
+        answer2: None,
+    }
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u8 = 24;
+    type Puzzle = Vec<(isize, isize)>;
+
+    fn parse_lines(lines: impl Iterator<Item = String>) -> Result<Self::Puzzle, ParseError> {
+        parse_tiles(lines)
+    }
+
+    fn part1(tiles: &Self::Puzzle) -> Option<String> {
+        Some(star_one(tiles).to_string())
+    }
+
+    fn part2(tiles: &Self::Puzzle) -> Option<String> {
+        Some(star_two(tiles).to_string())
+    }
+
+    fn generate(rng: &mut Random, size: usize) -> Option<Generated> {
+        Some(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    static TEST_DATA: &str = "sesenwnenenewseeswwswswwnenewsewsw
+neeenesenwnwwswnenewnwwsewnenwseswesw
+seswneswswsenwwnwse
+nwnwneseeswswnenewneswwnewseswneseene
+swweswneswnenwsewnwneneseenw
+eesenwseswswnenwswnwnwsewwnwsene
+sewnenenenesenwsewnenwwwse
+wenwwweseeeweswwwnwwe
+wsweesenenewnwwnwsenewsenwwsesesenwne
+neeswseenwwswnwswswnw
+nenwswwsewswnenenewsenwsenwnesesenew
+enewnwewneswsewnwswenweswnenwsenwsw
+sweneswneswneneenwnewenewwneswswnese
+swwesenesewenwneswnwwneseswwne
+enesenwswwswneneswsenwnewswseenwsese
+wnwnesenesenenwwnenwsewesewsesesew
+nenewswnwewswnenesenwnesewesw
+eneswnwswnwsenenwnwnwwseeswneewsenese
+neswnwewnwnwseenwseesewsenwsweewe
+wseweeenwnesenwwwswnew";
+
+    static SIMPLE_TEST_DATA: &str = "nwwswee";
+
+    #[test]
+    fn test_star_one() {
+        let lines = SIMPLE_TEST_DATA.lines().map(String::from);
+        let tiles = super::parse_tiles(lines).expect("Invalid test data");
+
+        let ans = super::star_one(&tiles);
+        assert_eq!(ans, 1);
+
+        let lines = TEST_DATA.lines().map(String::from);
+
+        let tiles = super::parse_tiles(lines).expect("Invalid test data");
+
+        let ans = super::star_one(&tiles);
+        assert_eq!(ans, 10);
+    }
+
+    #[test]
+    fn test_star_two() {
+        let lines = TEST_DATA.lines().map(String::from);
+        let tiles = super::parse_tiles(lines).expect("Invalid test data");
+
+        let ans = super::star_two(&tiles);
+        assert_eq!(ans, 2208);
+
+        assert_eq!(super::black_after_days(&tiles, 0), 10);
+        assert_eq!(super::black_after_days(&tiles, 1), 15);
+        assert_eq!(super::black_after_days(&tiles, 10), 37);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let lines: Vec<String> = vec![String::from("nwwswee"), String::from("esenx")];
+
+        let err = super::parse_tiles(lines.into_iter()).expect_err("Invalid direction accepted");
+        assert_eq!(err.line, Some(2));
+        assert_eq!(err.column, Some(4));
+    }
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..5 {
+            crate::generate::check::<super::Day24>(seed, 300);
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use std::vec::Vec;
+use log::debug;
+use rand::Rng;
+
+use crate::error::ParseError;
+use crate::generate::{Generated, Random};
+use crate::solution::Solution;
+
+pub fn calc_ek_using_modular_pow(b: usize, e: usize) -> usize {
+    let modulus = 20201227;
+    let mut c: usize = 1;
+    for _ in 0..e {
+        c = (c * b) % modulus
+    }
+
+    c
+}
+
+pub fn calc_e_using_modular_pow(key: usize) -> usize {
+    let modulus = 20201227;
+    let mut c: usize = 1;
+    let mut e: usize = 0;
+    while c != key {
+        c = (c * 7) % modulus;
+        e += 1
+    }
+
+    e
+}
+
+pub fn star_one(pubkeys: Vec<usize>) -> usize {
+    // pubkey_card = 7^loopsize % 2020227
+    let card_pubkey = pubkeys[0];
+    let door_pubkey = pubkeys[1];
+    let card_loopsize = calc_e_using_modular_pow(card_pubkey);
+    let door_loopsize = calc_e_using_modular_pow(door_pubkey);
+    let card_privkey = calc_ek_using_modular_pow(door_pubkey, card_loopsize);
+    let door_privkey = calc_ek_using_modular_pow(card_pubkey, door_loopsize);
+    assert_eq!(card_privkey, door_privkey);
+    debug!("Card: pubkey {} loopsize {} privkey {}", card_pubkey, card_loopsize, card_privkey);
+    debug!("Door: pubkey {} loopsize {} privkey {}", door_pubkey, door_loopsize, door_privkey);
+
+    card_privkey
+}
+
+/// Public keys for loop sizes of up to `size` thousand. The encryption key
+/// is worked out by square and multiply, not by looping.
+pub fn generate(rng: &mut Random, size: usize) -> Generated {
+    let modulus = 20201227;
+    let modular_pow = |base: usize, exponent: usize| {
+        let (mut result, mut base, mut exponent) = (1, base % modulus, exponent);
+        while exponent > 0 {
+            if exponent % 2 == 1 {
+                result = result * base % modulus;
+            }
+            base = base * base % modulus;
+            exponent /= 2;
+        }
+        result
+    };
+
+    let max_loop_size = size.max(1) * 1000;
+    let card_loop_size = rng.gen_range(1..=max_loop_size);
+    let door_loop_size = rng.gen_range(1..=max_loop_size);
+    let card_key = modular_pow(7, card_loop_size);
+    let door_key = modular_pow(7, door_loop_size);
+
+    Generated {
+        input: format!("{}\n{}", card_key, door_key),
+        answer1: Some(modular_pow(7, card_loop_size * door_loop_size).to_string()),
+        answer2: None,
+    }
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u8 = 25;
+    type Puzzle = Vec<usize>;
+
+    fn parse_lines(lines: impl Iterator<Item = String>) -> Result<Self::Puzzle, ParseError> {
+        let pubkeys: Vec<usize> = crate::parse_number_lines(Self::DAY, lines)?;
+        if pubkeys.len() != 2 {
+            return Err(ParseError::new(Self::DAY, "", "expected exactly two public keys"));
+        }
+        // Anything else never turns up when transforming subject number 7
+        for (i, key) in pubkeys.iter().enumerate() {
+            if *key == 0 || *key >= 20201227 {
+                return Err(ParseError::new(Self::DAY, &key.to_string(), "public key out of range").at_line(i + 1));
+            }
+        }
+        Ok(pubkeys)
+    }
+
+    fn part1(pubkeys: &Self::Puzzle) -> Option<String> {
+        Some(star_one(pubkeys.clone()).to_string())
+    }
+
+    fn part2(_pubkeys: &Self::Puzzle) -> Option<String> {
+        // Christmas day only has a single puzzle
+        None
+    }
+
+    fn generate(rng: &mut Random, size: usize) -> Option<Generated> {
+        Some(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    static TEST_DATA: &str = "5764801
+17807724";
+
+    #[test]
+    fn test_star_one() {
+        let pubkeys: Vec<usize> = TEST_DATA
+            .lines()
+            .map(|x| x.parse::<usize>().expect("Invalid test data"))
+            .collect();
+
+        let ans = super::star_one(pubkeys);
+        assert_eq!(ans, 14897079);
+    }
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..5 {
+            crate::generate::check::<super::Day25>(seed, 100);
+        }
+    }
+}
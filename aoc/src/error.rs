@@ -0,0 +1,115 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+/// Why (and where) a puzzle input could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    /// Line in the input, counting from 1
+    pub line: Option<usize>,
+    /// Column in that line, counting from 1
+    pub column: Option<usize>,
+    /// The offending part of the input
+    pub text: String,
+    pub reason: String,
+}
+
+impl ParseError {
+    pub fn new(day: u8, text: &str, reason: impl Into<String>) -> ParseError {
+        ParseError {
+            day,
+            line: None,
+            column: None,
+            text: text.to_string(),
+            reason: reason.into(),
+        }
+    }
+
+    pub fn at_line(mut self, line: usize) -> ParseError {
+        self.line = Some(line);
+        self
+    }
+
+    pub fn at_column(mut self, column: usize) -> ParseError {
+        self.column = Some(column);
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "day {}", self.day)?;
+        if let Some(line) = self.line {
+            write!(f, ", line {}", line)?;
+        }
+        if let Some(column) = self.column {
+            write!(f, ", column {}", column)?;
+        }
+        write!(f, ": {}", self.reason)?;
+        if !self.text.is_empty() {
+            write!(f, ": {:?}", self.text)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ParseError {}
+
+/// Why a puzzle input could not be read from a file or stream: either the
+/// reading itself failed, or what was read could not be parsed.
+#[derive(Debug)]
+pub enum ReadError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl From<io::Error> for ReadError {
+    fn from(e: io::Error) -> ReadError {
+        ReadError::Io(e)
+    }
+}
+
+impl From<ParseError> for ReadError {
+    fn from(e: ParseError) -> ReadError {
+        ReadError::Parse(e)
+    }
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReadError::Io(e) => write!(f, "cannot read input: {}", e),
+            ReadError::Parse(e) => e.fmt(f),
+        }
+    }
+}
+
+impl Error for ReadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ReadError::Io(e) => Some(e),
+            ReadError::Parse(e) => Some(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ParseError;
+
+    #[test]
+    fn test_display() {
+        let err = ParseError::new(8, "foo +1", "unknown operation");
+        assert_eq!(err.to_string(), "day 8: unknown operation: \"foo +1\"");
+
+        let err = err.at_line(3);
+        assert_eq!(err.to_string(), "day 8, line 3: unknown operation: \"foo +1\"");
+
+        let err = ParseError::new(3, "x", "invalid map tile").at_line(2).at_column(7);
+        assert_eq!(err.to_string(), "day 3, line 2, column 7: invalid map tile: \"x\"");
+
+        let err = ParseError::new(13, "", "missing bus schedule");
+        assert_eq!(err.to_string(), "day 13: missing bus schedule");
+    }
+}
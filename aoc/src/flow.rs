@@ -0,0 +1,170 @@
+//! Static analysis of programs for the `vm`, such as the boot code of day 8:
+//! where every instruction leads without running the program, and which
+//! instructions end up at its end.
+
+use std::collections::VecDeque;
+
+use crate::vm::{Branch, Operation, Program};
+
+/// The control flow graph of a program: an edge from every instruction to
+/// the ones that can run after it, with the end of the program as an extra
+/// node after the last instruction.
+#[derive(Debug)]
+pub struct ControlFlow {
+    successors: Vec<Vec<usize>>,
+    reaches_end: Vec<bool>,
+}
+
+/// Where the instruction at `index` can lead when it is `operation`: the
+/// instructions it may run next, with the end of the program for anything
+/// past the last one and nothing for a jump before the first one.
+fn successors(program: &Program, index: usize, operation: Operation) -> Vec<usize> {
+    let len = program.instructions.len();
+    let offset = program.instructions[index].offset().unwrap_or(1);
+    let deltas = match operation.definition().branch {
+        Branch::Never => vec![1],
+        Branch::Always => vec![offset],
+        Branch::Conditional => vec![1, offset],
+    };
+    deltas
+        .into_iter()
+        .map(|delta| index as isize + delta)
+        .filter(|&next| next >= 0)
+        .map(|next| (next as usize).min(len))
+        .collect()
+}
+
+fn flipped(operation: Operation) -> Option<Operation> {
+    match operation {
+        Operation::Jmp => Some(Operation::Nop),
+        Operation::Nop => Some(Operation::Jmp),
+        _ => None,
+    }
+}
+
+impl ControlFlow {
+    pub fn new(program: &Program) -> ControlFlow {
+        let len = program.instructions.len();
+        let successors: Vec<Vec<usize>> = program
+            .instructions
+            .iter()
+            .enumerate()
+            .map(|(index, instruction)| successors(program, index, instruction.operation))
+            .collect();
+
+        // Walk the edges backwards from the end to find all that lead there
+        let mut predecessors = vec![vec![]; len + 1];
+        for (index, next) in successors.iter().enumerate() {
+            for &next in next.iter() {
+                predecessors[next].push(index);
+            }
+        }
+        let mut reaches_end = vec![false; len + 1];
+        reaches_end[len] = true;
+        let mut queue = VecDeque::from(vec![len]);
+        while let Some(node) = queue.pop_front() {
+            for &index in predecessors[node].iter() {
+                if !reaches_end[index] {
+                    reaches_end[index] = true;
+                    queue.push_back(index);
+                }
+            }
+        }
+
+        ControlFlow { successors, reaches_end }
+    }
+
+    /// The instructions that can run after the one at `index`; the number of
+    /// instructions stands for the end of the program.
+    pub fn successors(&self, index: usize) -> &[usize] {
+        self.successors.get(index).map(|next| next.as_slice()).unwrap_or_default()
+    }
+
+    /// Whether the program can end when started at `index`.
+    pub fn reaches_end(&self, index: usize) -> bool {
+        self.reaches_end[index]
+    }
+}
+
+/// The one `jmp` or `nop` to flip so that the program ends instead of loops,
+/// in time linear in its length. The flip must be on the path the program
+/// takes before it loops, and lead to an instruction that ends the program
+/// by itself: the way there cannot pass the flipped instruction again, or
+/// that one would reach the end unflipped as well. Only for programs without
+/// conditional jumps, whose path depends on more than the code.
+pub fn repair(program: &Program) -> Option<usize> {
+    let flow = ControlFlow::new(program);
+    let len = program.instructions.len();
+    let mut visited = vec![false; len];
+    let mut index = 0;
+    while index < len && !visited[index] {
+        visited[index] = true;
+        let operation = program.instructions[index].operation;
+        if let Some(flipped) = flipped(operation) {
+            match successors(program, index, flipped)[..] {
+                [next] if flow.reaches_end(next) => return Some(index),
+                _ => (),
+            }
+        }
+        index = match flow.successors(index) {
+            [next] => *next,
+            _ => return None,
+        };
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::vm::Program;
+
+    #[test]
+    fn test_control_flow() {
+        let program = Program::assemble(
+            "
+    nop +0
+loop:
+    acc +1
+    jmp skip
+    acc +3
+    jmp loop
+    acc -99
+skip:
+    acc +1
+    jmp -4
+    acc +6
+",
+        )
+        .expect("Invalid test data");
+        let flow = super::ControlFlow::new(&program);
+        let successors: Vec<&[usize]> = (0..9).map(|index| flow.successors(index)).collect();
+        assert_eq!(successors, vec![[1], [2], [6], [4], [1], [6], [7], [3], [9]]);
+        let ends: Vec<usize> = (0..=9).filter(|&index| flow.reaches_end(index)).collect();
+        assert_eq!(ends, vec![8, 9]);
+
+        // Flipping jmp -4 makes it fall through to acc +6
+        assert_eq!(super::repair(&program), Some(7));
+    }
+
+    #[test]
+    fn test_repair_edges() {
+        let program = Program::assemble("jmp -1\nacc +1").expect("Invalid program");
+        assert_eq!(super::repair(&program), Some(0));
+
+        // Flipping the first jmp -1 only leads into the other one
+        let program = Program::assemble("acc +1\njmp -1\njmp -1").expect("Invalid program");
+        assert_eq!(super::repair(&program), None);
+    }
+
+    #[test]
+    fn test_conditional_jumps() {
+        let program = Program::assemble("inp a\nloop: jnz a loop\njmp -3").expect("Invalid program");
+        let flow = super::ControlFlow::new(&program);
+        assert_eq!(flow.successors(1), [2, 1]);
+        assert_eq!(flow.successors(2), &[] as &[usize]);
+        assert!(!flow.reaches_end(0));
+
+        // Where the loop goes depends on the input
+        assert_eq!(super::repair(&program), None);
+    }
+}
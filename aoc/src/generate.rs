@@ -0,0 +1,62 @@
+//! Random puzzle inputs, to try the solvers on more than the one real input
+//! per day. Every day builds its own inputs in its module; the same seed and
+//! size always give the same input.
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+/// The random number generator all generators draw from. ChaCha gives the
+/// same numbers for a seed on every platform.
+pub type Random = ChaCha8Rng;
+
+pub fn random(seed: u64) -> Random {
+    Random::seed_from_u64(seed)
+}
+
+/// A generated puzzle input.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Generated {
+    pub input: String,
+    /// Answers the input was built to have, `None` where they cannot be
+    /// known without solving the puzzle
+    pub answer1: Option<String>,
+    pub answer2: Option<String>,
+}
+
+/// A random word of lowercase letters, for the names in an input.
+pub fn word(rng: &mut Random, min_len: usize, max_len: usize) -> String {
+    let len = rng.gen_range(min_len..=max_len);
+    (0..len).map(|_| rng.gen_range(b'a'..=b'z') as char).collect()
+}
+
+/// Generate an input for a day and check that it parses and that the solver
+/// finds the answers it was built to have.
+#[cfg(test)]
+pub fn check<S: crate::Solution>(seed: u64, size: usize) -> Generated {
+    let generated = S::generate(&mut random(seed), size).expect("Day has no generator");
+    assert_eq!(
+        S::generate(&mut random(seed), size),
+        Some(generated.clone()),
+        "Same seed gives another input"
+    );
+
+    let puzzle = S::parse(&generated.input).unwrap_or_else(|e| panic!("{}\n{}", e, generated.input));
+    if generated.answer1.is_some() {
+        assert_eq!(S::part1(&puzzle), generated.answer1, "Seed {} size {}", seed, size);
+    }
+    if generated.answer2.is_some() {
+        assert_eq!(S::part2(&puzzle), generated.answer2, "Seed {} size {}", seed, size);
+    }
+    generated
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_every_day() {
+        for solver in crate::registry::all() {
+            let generated = solver.generate(1, 4);
+            assert!(generated.is_some(), "Day {} has no generator", solver.day());
+        }
+    }
+}
@@ -0,0 +1,343 @@
+use std::fmt;
+use std::iter;
+use std::ops::{Index, IndexMut};
+
+use crate::error::ParseError;
+
+/// Steps to the 4 cells sharing a side, as (x, y) with y pointing down.
+pub const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Steps to all 8 surrounding cells, diagonals included.
+pub const ADJACENT: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// A dense rectangle of cells, stored row by row. Positions are (x, y) with
+/// (0, 0) in the top left corner, as in the puzzle text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+    // The pattern repeats endlessly to the right, like the day 3 map
+    wrap_x: bool,
+}
+
+impl<T> Grid<T> {
+    pub fn from_fn(width: usize, height: usize, mut cell: impl FnMut(usize, usize) -> T) -> Grid<T> {
+        let mut cells = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                cells.push(cell(x, y));
+            }
+        }
+        Grid {
+            cells,
+            width,
+            height,
+            wrap_x: false,
+        }
+    }
+
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: vec![fill; width * height],
+            width,
+            height,
+            wrap_x: false,
+        }
+    }
+
+    /// Parse a map with one character per cell. `cell` gives the reason why
+    /// a character is not allowed; rows must all have the same width.
+    pub fn parse(day: u8, input: &str, cell: impl Fn(char) -> Result<T, &'static str>) -> Result<Grid<T>, ParseError> {
+        Grid::from_lines(day, input.lines(), cell)
+    }
+
+    /// Like `parse`, for a map that is read one row at a time.
+    pub fn from_lines<L>(
+        day: u8,
+        lines: impl Iterator<Item = L>,
+        cell: impl Fn(char) -> Result<T, &'static str>,
+    ) -> Result<Grid<T>, ParseError>
+    where
+        L: AsRef<str>,
+    {
+        let mut cells = vec![];
+        let mut width = 0;
+        let mut height = 0;
+        for (y, line) in lines.enumerate() {
+            let line = line.as_ref();
+            let before = cells.len();
+            for (x, ch) in line.chars().enumerate() {
+                let value = cell(ch).map_err(|reason| {
+                    ParseError::new(day, &ch.to_string(), reason)
+                        .at_line(y + 1)
+                        .at_column(x + 1)
+                })?;
+                cells.push(value);
+            }
+
+            let row_width = cells.len() - before;
+            if y == 0 {
+                width = row_width;
+            } else if row_width != width {
+                return Err(ParseError::new(day, line, format!("row is not {} characters wide", width)).at_line(y + 1));
+            }
+            height += 1;
+        }
+        if cells.is_empty() {
+            return Err(ParseError::new(day, "", "empty map"));
+        }
+
+        Ok(Grid {
+            cells,
+            width,
+            height,
+            wrap_x: false,
+        })
+    }
+
+    /// Make the grid repeat endlessly in the x direction.
+    pub fn wrapping_x(mut self) -> Grid<T> {
+        self.wrap_x = true;
+        self
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn index_of(&self, x: isize, y: isize) -> Option<usize> {
+        if y < 0 || y as usize >= self.height || self.width == 0 {
+            return None;
+        }
+        let x = if self.wrap_x {
+            x.rem_euclid(self.width as isize)
+        } else if x < 0 || x as usize >= self.width {
+            return None;
+        } else {
+            x
+        };
+        Some(y as usize * self.width + x as usize)
+    }
+
+    pub fn contains(&self, x: isize, y: isize) -> bool {
+        self.index_of(x, y).is_some()
+    }
+
+    /// The cell at a position, or `None` outside the grid.
+    pub fn get(&self, x: isize, y: isize) -> Option<&T> {
+        self.index_of(x, y).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, x: isize, y: isize) -> Option<&mut T> {
+        match self.index_of(x, y) {
+            Some(i) => Some(&mut self.cells[i]),
+            None => None,
+        }
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// All cells, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        self.cells.iter()
+    }
+
+    /// All positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// The cells one step away in each of `directions`, skipping those that
+    /// fall outside the grid.
+    pub fn neighbours<'a>(
+        &'a self,
+        x: usize,
+        y: usize,
+        directions: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = &'a T> + 'a {
+        directions
+            .iter()
+            .filter_map(move |(dx, dy)| self.get(x as isize + dx, y as isize + dy))
+    }
+
+    /// The cells from `start` on, taking `step` every time, until the walk
+    /// leaves the grid or comes back to `start`, as it does going round a
+    /// grid that wraps.
+    pub fn walk(&self, start: (isize, isize), step: (isize, isize)) -> impl Iterator<Item = &T> + '_ {
+        let first = self.index_of(start.0, start.1);
+        iter::successors(Some(start), move |(x, y)| Some((x + step.0, y + step.1)))
+            .map(move |(x, y)| self.index_of(x, y))
+            .enumerate()
+            .map_while(move |(i, index)| match index {
+                Some(index) if i == 0 || Some(index) != first => Some(&self.cells[index]),
+                _ => None,
+            })
+    }
+
+    /// Cast a ray from (x, y) in `direction` and give the first cell that
+    /// can be `seen`, if any before the edge of the grid.
+    pub fn first_visible(
+        &self,
+        x: usize,
+        y: usize,
+        direction: (isize, isize),
+        seen: impl Fn(&T) -> bool,
+    ) -> Option<&T> {
+        let start = (x as isize + direction.0, y as isize + direction.1);
+        self.walk(start, direction).find(|cell| seen(cell))
+    }
+
+    /// Turn the grid a quarter clockwise.
+    pub fn rotate_cw(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let height = self.height;
+        Grid {
+            wrap_x: self.wrap_x,
+            ..Grid::from_fn(self.height, self.width, |x, y| self[(y, height - 1 - x)].clone())
+        }
+    }
+
+    /// Mirror the grid top to bottom.
+    pub fn flip_vertical(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let height = self.height;
+        Grid {
+            wrap_x: self.wrap_x,
+            ..Grid::from_fn(self.width, self.height, |x, y| self[(x, height - 1 - y)].clone())
+        }
+    }
+
+    /// Draw the grid with one character per cell and a line per row.
+    pub fn render(&self, cell: impl Fn(&T) -> char) -> String {
+        let mut result = String::with_capacity((self.width + 1) * self.height);
+        for y in 0..self.height {
+            result.extend(self.row(y).iter().map(&cell));
+            result.push('\n');
+        }
+        result
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(x < self.width && y < self.height, "({}, {}) outside of grid", x, y);
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(x < self.width && y < self.height, "({}, {}) outside of grid", x, y);
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+impl<T> fmt::Display for Grid<T>
+where
+    T: Copy + Into<char>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.render(|&cell| cell.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Grid;
+
+    static TEST_DATA: &str = "#..
+.#.
+..#
+##.";
+
+    fn parse(input: &str) -> Result<Grid<bool>, crate::ParseError> {
+        Grid::parse(0, input, |ch| match ch {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err("invalid cell"),
+        })
+    }
+
+    fn render(grid: &Grid<bool>) -> String {
+        grid.render(|&on| if on { '#' } else { '.' })
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = parse(TEST_DATA).expect("Invalid test data");
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 4);
+        assert_eq!(grid.get(1, 1), Some(&true));
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.get(0, -1), None);
+        assert_eq!(render(&grid), format!("{}\n", TEST_DATA));
+
+        let err = parse("#.\n#x").unwrap_err();
+        assert_eq!((err.line, err.column), (Some(2), Some(2)));
+        let err = parse("#.\n#").unwrap_err();
+        assert_eq!(err.line, Some(2));
+        assert!(parse("").is_err());
+    }
+
+    #[test]
+    fn test_wrap_and_walk() {
+        let grid = parse(TEST_DATA).expect("Invalid test data").wrapping_x();
+        assert_eq!(grid.get(4, 1), Some(&true));
+        assert_eq!(grid.get(-1, 2), Some(&true));
+        assert_eq!(grid.get(0, 4), None);
+
+        let walked: Vec<bool> = grid.walk((0, 0), (2, 1)).copied().collect();
+        assert_eq!(walked, vec![true, false, false, true]);
+
+        // Round the grid once, and not at all without a step
+        assert_eq!(grid.walk((1, 2), (-2, 0)).count(), 3);
+        assert_eq!(grid.walk((1, 2), (0, 0)).count(), 1);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = parse(TEST_DATA).expect("Invalid test data");
+        assert_eq!(grid.neighbours(0, 0, &super::ADJACENT).filter(|&&x| x).count(), 1);
+        assert_eq!(grid.neighbours(1, 2, &super::ADJACENT).filter(|&&x| x).count(), 4);
+        assert_eq!(grid.neighbours(1, 2, &super::ORTHOGONAL).filter(|&&x| x).count(), 3);
+
+        // Looking past the empty cells
+        assert_eq!(grid.first_visible(2, 0, (-1, 1), |&x| x), Some(&true));
+        assert_eq!(grid.first_visible(2, 0, (0, 1), |&x| x), Some(&true));
+        assert_eq!(grid.first_visible(1, 3, (-1, -1), |&x| x), None);
+    }
+
+    #[test]
+    fn test_rotate_and_flip() {
+        let grid = parse(TEST_DATA).expect("Invalid test data");
+        assert_eq!(render(&grid.rotate_cw()), "#..#\n#.#.\n.#..\n");
+        assert_eq!(render(&grid.flip_vertical()), "##.\n..#\n.#.\n#..\n");
+        assert_eq!(grid.rotate_cw().rotate_cw().rotate_cw().rotate_cw(), grid);
+    }
+}
@@ -0,0 +1,65 @@
+use std::io::{self, BufRead};
+
+/// The lines of a reader as plain strings, so that parsers do not have to
+/// deal with read errors. Reading stops at the first error, which is kept
+/// for `finish` to report once parsing is done.
+pub struct Lines<B> {
+    lines: io::Lines<B>,
+    error: Option<io::Error>,
+}
+
+impl<B: BufRead> Lines<B> {
+    pub fn new(reader: B) -> Lines<B> {
+        Lines {
+            lines: reader.lines(),
+            error: None,
+        }
+    }
+
+    /// The error that ended reading early, if any.
+    pub fn finish(self) -> io::Result<()> {
+        match self.error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<B: BufRead> Iterator for Lines<B> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        if self.error.is_some() {
+            return None;
+        }
+        match self.lines.next()? {
+            Ok(line) => Some(line),
+            Err(e) => {
+                self.error = Some(e);
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use super::Lines;
+
+    #[test]
+    fn test_lines() {
+        let mut lines = Lines::new("nop +0\r\nacc +1\n\njmp -2".as_bytes());
+        assert_eq!(lines.by_ref().collect::<Vec<String>>(), vec!["nop +0", "acc +1", "", "jmp -2"]);
+        assert!(lines.finish().is_ok());
+    }
+
+    #[test]
+    fn test_read_error() {
+        // A lone 0xff byte is not valid UTF-8
+        let mut lines = Lines::new(&b"1721\n979\n\xff\n366\n"[..]);
+        assert_eq!(lines.by_ref().collect::<Vec<String>>(), vec!["1721", "979"]);
+        assert_eq!(lines.finish().unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+}
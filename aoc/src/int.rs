@@ -0,0 +1,128 @@
+//! Integers for answers that can grow far beyond the numbers in the input,
+//! like products of IDs or numbers of arrangements. By default these are
+//! `i64`s with every operation checked, so that an answer too big to fit is
+//! an `Overflow` instead of a silently wrapped number. With the `bigint`
+//! feature they are `BigInt`s, which never overflow.
+
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt::{self, Display};
+
+use log::error;
+
+#[cfg(not(feature = "bigint"))]
+pub type Int = i64;
+
+#[cfg(feature = "bigint")]
+pub type Int = num_bigint::BigInt;
+
+/// An answer did not fit in an `Int`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow;
+
+impl Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "answer does not fit in 64 bits, build with the bigint feature")
+    }
+}
+
+impl Error for Overflow {}
+
+pub fn from<T>(x: T) -> Result<Int, Overflow>
+where
+    Int: TryFrom<T>,
+{
+    Int::try_from(x).map_err(|_| Overflow)
+}
+
+#[cfg(not(feature = "bigint"))]
+pub fn add(a: &Int, b: &Int) -> Result<Int, Overflow> {
+    a.checked_add(*b).ok_or(Overflow)
+}
+
+#[cfg(feature = "bigint")]
+pub fn add(a: &Int, b: &Int) -> Result<Int, Overflow> {
+    Ok(a + b)
+}
+
+#[cfg(not(feature = "bigint"))]
+pub fn mul(a: &Int, b: &Int) -> Result<Int, Overflow> {
+    a.checked_mul(*b).ok_or(Overflow)
+}
+
+#[cfg(feature = "bigint")]
+pub fn mul(a: &Int, b: &Int) -> Result<Int, Overflow> {
+    Ok(a * b)
+}
+
+#[cfg(not(feature = "bigint"))]
+pub fn pow(a: &Int, exp: u32) -> Result<Int, Overflow> {
+    a.checked_pow(exp).ok_or(Overflow)
+}
+
+#[cfg(feature = "bigint")]
+pub fn pow(a: &Int, exp: u32) -> Result<Int, Overflow> {
+    Ok(num_traits::Pow::pow(a, exp))
+}
+
+#[cfg(not(feature = "bigint"))]
+pub fn is_multiple_of(a: &Int, b: &Int) -> bool {
+    a % b == 0
+}
+
+#[cfg(feature = "bigint")]
+pub fn is_multiple_of(a: &Int, b: &Int) -> bool {
+    a % b == Int::from(0)
+}
+
+/// An answer for `Solution::part1` or `part2`. An overflow is logged, so
+/// that it does not look like the input has no answer.
+pub fn answer(day: u8, result: Result<Int, Overflow>) -> Option<String> {
+    match result {
+        Ok(x) => Some(x.to_string()),
+        Err(e) => {
+            error!("Day {}: {}", day, e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Int;
+
+    #[test]
+    fn test_arithmetic() {
+        let a: Int = 3.into();
+        let b: Int = 4.into();
+        assert_eq!(super::add(&a, &b), Ok(7.into()));
+        assert_eq!(super::mul(&a, &b), Ok(12.into()));
+        assert_eq!(super::pow(&a, 4), Ok(81.into()));
+        assert_eq!(super::from(12usize), Ok(12.into()));
+        assert!(super::is_multiple_of(&b, &2.into()));
+        assert!(!super::is_multiple_of(&a, &2.into()));
+    }
+
+    #[cfg(not(feature = "bigint"))]
+    #[test]
+    fn test_overflow() {
+        use super::Overflow;
+
+        let big: Int = 1 << 62;
+        assert_eq!(super::add(&big, &big), Err(Overflow));
+        assert_eq!(super::mul(&big, &2), Err(Overflow));
+        assert_eq!(super::pow(&2, 63), Err(Overflow));
+        assert_eq!(super::from(u64::MAX), Err(Overflow));
+        assert_eq!(super::answer(20, Err(Overflow)), None);
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn test_no_overflow() {
+        let big: Int = (1i64 << 62).into();
+        assert_eq!(super::mul(&big, &big).unwrap().to_string(), "21267647932558653966460912964485513216");
+        assert_eq!(super::pow(&2.into(), 100).unwrap().to_string(), "1267650600228229401496703205376");
+        assert_eq!(super::from(u64::MAX).map(|x| x.to_string()), Ok(u64::MAX.to_string()));
+        assert!(super::add(&big, &big).is_ok());
+    }
+}
@@ -0,0 +1,76 @@
+//! Advent of Code 2020 puzzle solutions, one module per day.
+//!
+//! Every module exposes the parsed puzzle types and the `star_one` / `star_two`
+//! solvers so they can be driven from other code instead of only from the
+//! per-day binaries. On top of that every day implements `Solution`, and the
+//! `registry` holds all of them so they can be run generically. Days can also
+//! build random inputs with known answers, see `generate`.
+
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{self, BufRead};
+use std::path::Path;
+use std::str::FromStr;
+
+pub mod automaton;
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+pub mod error;
+pub mod flow;
+pub mod generate;
+pub mod grid;
+pub mod input;
+pub mod int;
+pub mod registry;
+pub mod solution;
+pub mod vm;
+
+pub use error::{ParseError, ReadError};
+pub use grid::Grid;
+pub use solution::{Solution, Solver, Variant};
+
+pub fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
+where
+    P: AsRef<Path>,
+{
+    let file = File::open(filename)?;
+    Ok(io::BufReader::new(file).lines())
+}
+
+/// Parse an input with one number on every line.
+pub fn parse_number_lines<T>(day: u8, lines: impl Iterator<Item = String>) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    lines
+        .enumerate()
+        .map(|(i, x)| {
+            x.parse::<T>()
+                .map_err(|e| ParseError::new(day, &x, format!("invalid number ({})", e)).at_line(i + 1))
+        })
+        .collect()
+}
@@ -0,0 +1,140 @@
+use crate::solution::Solver;
+use crate::*;
+
+static SOLVERS: &[&dyn Solver] = &[
+    &day01::Day01,
+    &day02::Day02,
+    &day03::Day03,
+    &day04::Day04,
+    &day05::Day05,
+    &day06::Day06,
+    &day07::Day07,
+    &day08::Day08,
+    &day09::Day09,
+    &day10::Day10,
+    &day11::Day11,
+    &day12::Day12,
+    &day13::Day13,
+    &day14::Day14,
+    &day15::Day15,
+    &day16::Day16,
+    &day17::Day17,
+    &day18::Day18,
+    &day19::Day19,
+    &day20::Day20,
+    &day21::Day21,
+    &day22::Day22,
+    &day23::Day23,
+    &day24::Day24,
+    &day25::Day25,
+];
+
+/// All days, in calendar order.
+pub fn all() -> &'static [&'static dyn Solver] {
+    SOLVERS
+}
+
+pub fn get(day: u8) -> Option<&'static dyn Solver> {
+    SOLVERS.iter().find(|s| s.day() == day).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_all_days_registered() {
+        let days: Vec<u8> = super::all().iter().map(|s| s.day()).collect();
+        assert_eq!(days, (1..=25).collect::<Vec<u8>>());
+
+        assert!(super::get(0).is_none());
+        assert!(super::get(26).is_none());
+    }
+
+    #[test]
+    fn test_solve_through_registry() {
+        let solver = super::get(1).expect("Day 1 not registered");
+        let (part1, part2) = solver
+            .solve("1721\n979\n366\n299\n675\n1456\n")
+            .expect("Invalid test data");
+        assert_eq!(part1, Some(String::from("514579")));
+        assert_eq!(part2, Some(String::from("241861950")));
+
+        let solver = super::get(8).expect("Day 8 not registered");
+        assert!(solver.solve("nop +0\nfoo +1\n").is_err());
+    }
+
+    #[test]
+    fn test_read() {
+        let solver = super::get(1).expect("Day 1 not registered");
+        let puzzle = solver.read(&mut "1721\n979\n366\n299\n675\n1456\n".as_bytes()).expect("Invalid test data");
+        assert_eq!(solver.part1(puzzle.as_ref()), Some(String::from("514579")));
+
+        let err = solver.read(&mut &b"1721\n979\n\xff\n"[..]).expect_err("Invalid UTF-8 accepted");
+        assert!(matches!(err, crate::ReadError::Io(_)));
+        let err = solver.read(&mut "1721\nx\n".as_bytes()).expect_err("Invalid number accepted");
+        assert!(matches!(err, crate::ReadError::Parse(ref e) if e.line == Some(2)));
+    }
+
+    #[test]
+    fn test_variants_agree() {
+        let examples = [
+            (8, "nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6\n"),
+            (13, "939\n7,13,x,x,59,x,31,19\n"),
+        ];
+        for (day, input) in examples.iter() {
+            let solver = super::get(*day).expect("Day not registered");
+            let puzzle = solver.parse(input).expect("Invalid test data");
+            for part in 1..=2 {
+                let variants = solver.variants(part);
+                let answer = solver.solve_variant(part, variants[0], puzzle.as_ref());
+                for variant in variants {
+                    assert_eq!(solver.solve_variant(part, variant, puzzle.as_ref()), answer);
+                }
+            }
+        }
+        assert_eq!(super::get(8).unwrap().variants(2), vec!["dfs", "brute", "cfg"]);
+    }
+
+    /// Examples saved from the puzzle pages with `aoc examples`, in
+    /// `dayN/examples/` as `partN.txt` with the answer in `partN.answer`.
+    #[test]
+    fn test_examples() {
+        let root = std::path::Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/.."));
+        for solver in super::all() {
+            let dir = root.join(format!("day{}", solver.day())).join("examples");
+            for part in 1..=2 {
+                let read = |extension| std::fs::read_to_string(dir.join(format!("part{}.{}", part, extension)));
+                let (input, answer) = match (read("txt"), read("answer")) {
+                    (Ok(input), Ok(answer)) => (input, answer),
+                    _ => continue,
+                };
+                let puzzle = solver.parse(&input).expect("Invalid example");
+                for variant in solver.variants(part) {
+                    let ans = solver.solve_variant(part, variant, puzzle.as_ref());
+                    assert_eq!(ans.as_deref(), Some(answer.trim()), "day {} part {} {}", solver.day(), part, variant);
+                }
+            }
+        }
+    }
+
+    /// Inputs that once made a parser or solver panic, found by the fuzz
+    /// targets in `fuzz/`. Each must now either fail to parse or be solved.
+    #[test]
+    fn test_fuzz_regressions() {
+        let corpus = std::path::Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/../fuzz/regressions"));
+        for solver in super::all() {
+            let dir = corpus.join(format!("day{:02}", solver.day()));
+            let entries = match std::fs::read_dir(&dir) {
+                Ok(entries) => entries,
+                Err(_) => continue,
+            };
+            for entry in entries {
+                let path = entry.expect("Unreadable corpus directory").path();
+                let data = std::fs::read(&path).expect("Unreadable corpus file");
+                if let Ok(puzzle) = solver.parse(&String::from_utf8_lossy(&data)) {
+                    solver.part1(puzzle.as_ref());
+                    solver.part2(puzzle.as_ref());
+                }
+            }
+        }
+    }
+}
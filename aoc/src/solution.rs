@@ -0,0 +1,123 @@
+use std::any::Any;
+use std::io::BufRead;
+
+use crate::error::{ParseError, ReadError};
+use crate::generate::{self, Generated, Random};
+use crate::input::Lines;
+
+/// A named implementation of one part of a puzzle.
+pub type Variant<P> = (&'static str, fn(&P) -> Option<String>);
+
+/// One day of the calendar: how to turn the puzzle input into something
+/// solvable, and how to solve both parts from that.
+pub trait Solution {
+    /// Day of the month this puzzle was released on.
+    const DAY: u8;
+
+    /// The parsed puzzle input.
+    type Puzzle: 'static;
+
+    /// Parse the input one line at a time, so that it never has to be in
+    /// memory as a whole.
+    fn parse_lines(lines: impl Iterator<Item = String>) -> Result<Self::Puzzle, ParseError>;
+
+    fn parse(input: &str) -> Result<Self::Puzzle, ParseError> {
+        Self::parse_lines(input.lines().map(String::from))
+    }
+
+    /// Answer to the first part, or `None` if the input has no answer.
+    fn part1(puzzle: &Self::Puzzle) -> Option<String>;
+
+    /// Answer to the second part, or `None` if the input has no answer (or
+    /// the day has no second part at all).
+    fn part2(puzzle: &Self::Puzzle) -> Option<String>;
+
+    /// All implementations of a part, by name. The first one should be the
+    /// one `part1` or `part2` uses; days that only have one implementation
+    /// can leave this as is.
+    fn variants(part: u8) -> Vec<Variant<Self::Puzzle>> {
+        match part {
+            1 => vec![("default", Self::part1)],
+            _ => vec![("default", Self::part2)],
+        }
+    }
+
+    /// A random input of about `size` elements (lines, tiles, ...), with the
+    /// answers it was built to have. `None` for days without a generator.
+    fn generate(_rng: &mut Random, _size: usize) -> Option<Generated> {
+        None
+    }
+}
+
+/// Type-erased version of `Solution`, so that all days can be kept in a
+/// single table and driven without knowing their puzzle types.
+pub trait Solver: Sync {
+    fn day(&self) -> u8;
+    fn parse_lines(&self, lines: &mut dyn Iterator<Item = String>) -> Result<Box<dyn Any>, ParseError>;
+    fn part1(&self, puzzle: &dyn Any) -> Option<String>;
+    fn part2(&self, puzzle: &dyn Any) -> Option<String>;
+    fn variants(&self, part: u8) -> Vec<&'static str>;
+    fn solve_variant(&self, part: u8, variant: &str, puzzle: &dyn Any) -> Option<String>;
+    fn generate(&self, seed: u64, size: usize) -> Option<Generated>;
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, ParseError> {
+        self.parse_lines(&mut input.lines().map(String::from))
+    }
+
+    /// Parse everything `reader` gives, be it a file, stdin or a string in
+    /// memory. A read error wins over the parse error it probably caused.
+    fn read(&self, reader: &mut dyn BufRead) -> Result<Box<dyn Any>, ReadError> {
+        let mut lines = Lines::new(reader);
+        let puzzle = self.parse_lines(&mut lines);
+        lines.finish()?;
+        Ok(puzzle?)
+    }
+
+    fn solve(&self, input: &str) -> Result<(Option<String>, Option<String>), ParseError> {
+        let puzzle = self.parse(input)?;
+        Ok((self.part1(puzzle.as_ref()), self.part2(puzzle.as_ref())))
+    }
+}
+
+impl<S> Solver for S
+where
+    S: Solution + Sync,
+{
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn parse_lines(&self, lines: &mut dyn Iterator<Item = String>) -> Result<Box<dyn Any>, ParseError> {
+        Ok(Box::new(S::parse_lines(lines)?))
+    }
+
+    fn part1(&self, puzzle: &dyn Any) -> Option<String> {
+        S::part1(downcast::<S>(puzzle))
+    }
+
+    fn part2(&self, puzzle: &dyn Any) -> Option<String> {
+        S::part2(downcast::<S>(puzzle))
+    }
+
+    fn variants(&self, part: u8) -> Vec<&'static str> {
+        S::variants(part).into_iter().map(|(name, _)| name).collect()
+    }
+
+    fn solve_variant(&self, part: u8, variant: &str, puzzle: &dyn Any) -> Option<String> {
+        let (_, solve) = S::variants(part)
+            .into_iter()
+            .find(|(name, _)| *name == variant)
+            .unwrap_or_else(|| panic!("Day {} part {} has no variant {}", S::DAY, part, variant));
+        solve(downcast::<S>(puzzle))
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> Option<Generated> {
+        S::generate(&mut generate::random(seed), size)
+    }
+}
+
+fn downcast<S: Solution>(puzzle: &dyn Any) -> &S::Puzzle {
+    puzzle
+        .downcast_ref::<S::Puzzle>()
+        .unwrap_or_else(|| panic!("Puzzle passed to day {} was not parsed by it", S::DAY))
+}
@@ -0,0 +1,807 @@
+//! The handheld game console of day 8 as a small virtual machine, to reuse
+//! for any puzzle with assembly-like code. What every instruction looks like
+//! and does is described once, in `INSTRUCTION_SET`; parsing, running,
+//! assembling and disassembling all go by that table.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+
+use crate::error::ParseError;
+
+// The handheld first shows up on day 8, so its errors are reported for that day
+const DAY: u8 = 8;
+
+/// Index of a register in `REGISTERS`.
+pub type Register = usize;
+
+/// The names of the registers. Day 8 only uses the accumulator.
+pub const REGISTERS: &[&str] = &["acc", "a", "b", "c", "d"];
+
+pub const REGISTER_COUNT: usize = REGISTERS.len();
+
+pub const ACC: Register = 0;
+
+/// The operations, in the order of `INSTRUCTION_SET`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Operation {
+    Nop,
+    Acc,
+    Jmp,
+    Jz,
+    Jnz,
+    Cpy,
+    Add,
+    Mul,
+    Inp,
+    Out,
+}
+
+/// What an operand of an operation may be.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Kind {
+    /// A number
+    Value,
+    /// A number of instructions to jump, or a label in assembly
+    Offset,
+    /// A register to write to
+    Register,
+    /// A number or a register to read from
+    Source,
+}
+
+/// How an operation changes the instruction pointer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Branch {
+    /// On to the next instruction
+    Never,
+    /// Always by its offset
+    Always,
+    /// Either by its offset or on to the next instruction
+    Conditional,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Operand {
+    Value(isize),
+    Register(Register),
+}
+
+/// Why an instruction could not run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Fault {
+    JumpOutOfBounds,
+    NoInput,
+    Overflow,
+}
+
+impl fmt::Display for Fault {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Fault::JumpOutOfBounds => write!(f, "jumps out of the program"),
+            Fault::NoInput => write!(f, "reads past the end of the input"),
+            Fault::Overflow => write!(f, "overflows"),
+        }
+    }
+}
+
+/// Everything about one operation.
+pub struct Definition {
+    pub operation: Operation,
+    pub mnemonic: &'static str,
+    pub operands: &'static [Kind],
+    pub branch: Branch,
+    /// Carry out the instruction, returning how far to move the instruction
+    /// pointer. Operands it does not have are `Value(0)`.
+    pub execute: fn(&mut CPU, [Operand; 2]) -> Result<isize, Fault>,
+}
+
+pub static INSTRUCTION_SET: [Definition; 10] = [
+    Definition {
+        operation: Operation::Nop,
+        mnemonic: "nop",
+        // Never used, but it could have been an offset (day 8 part two)
+        operands: &[Kind::Offset],
+        branch: Branch::Never,
+        execute: |_, _| Ok(1),
+    },
+    Definition {
+        operation: Operation::Acc,
+        mnemonic: "acc",
+        operands: &[Kind::Value],
+        branch: Branch::Never,
+        execute: |cpu, [value, _]| {
+            let sum = cpu.registers[ACC].checked_add(cpu.get(value)).ok_or(Fault::Overflow)?;
+            cpu.registers[ACC] = sum;
+            Ok(1)
+        },
+    },
+    Definition {
+        operation: Operation::Jmp,
+        mnemonic: "jmp",
+        operands: &[Kind::Offset],
+        branch: Branch::Always,
+        execute: |cpu, [offset, _]| Ok(cpu.get(offset)),
+    },
+    Definition {
+        operation: Operation::Jz,
+        mnemonic: "jz",
+        operands: &[Kind::Source, Kind::Offset],
+        branch: Branch::Conditional,
+        execute: |cpu, [value, offset]| Ok(if cpu.get(value) == 0 { cpu.get(offset) } else { 1 }),
+    },
+    Definition {
+        operation: Operation::Jnz,
+        mnemonic: "jnz",
+        operands: &[Kind::Source, Kind::Offset],
+        branch: Branch::Conditional,
+        execute: |cpu, [value, offset]| Ok(if cpu.get(value) != 0 { cpu.get(offset) } else { 1 }),
+    },
+    Definition {
+        operation: Operation::Cpy,
+        mnemonic: "cpy",
+        operands: &[Kind::Source, Kind::Register],
+        branch: Branch::Never,
+        execute: |cpu, [value, register]| {
+            cpu.set(register, cpu.get(value));
+            Ok(1)
+        },
+    },
+    Definition {
+        operation: Operation::Add,
+        mnemonic: "add",
+        operands: &[Kind::Register, Kind::Source],
+        branch: Branch::Never,
+        execute: |cpu, [register, value]| {
+            let sum = cpu.get(register).checked_add(cpu.get(value)).ok_or(Fault::Overflow)?;
+            cpu.set(register, sum);
+            Ok(1)
+        },
+    },
+    Definition {
+        operation: Operation::Mul,
+        mnemonic: "mul",
+        operands: &[Kind::Register, Kind::Source],
+        branch: Branch::Never,
+        execute: |cpu, [register, value]| {
+            let product = cpu.get(register).checked_mul(cpu.get(value)).ok_or(Fault::Overflow)?;
+            cpu.set(register, product);
+            Ok(1)
+        },
+    },
+    Definition {
+        operation: Operation::Inp,
+        mnemonic: "inp",
+        operands: &[Kind::Register],
+        branch: Branch::Never,
+        execute: |cpu, [register, _]| {
+            let value = cpu.input.pop_front().ok_or(Fault::NoInput)?;
+            cpu.set(register, value);
+            Ok(1)
+        },
+    },
+    Definition {
+        operation: Operation::Out,
+        mnemonic: "out",
+        operands: &[Kind::Source],
+        branch: Branch::Never,
+        execute: |cpu, [value, _]| {
+            let value = cpu.get(value);
+            cpu.output.push(value);
+            Ok(1)
+        },
+    },
+];
+
+impl Operation {
+    pub fn definition(self) -> &'static Definition {
+        &INSTRUCTION_SET[self as usize]
+    }
+
+    pub fn from_mnemonic(mnemonic: &str) -> Option<Operation> {
+        INSTRUCTION_SET
+            .iter()
+            .find(|definition| definition.mnemonic == mnemonic)
+            .map(|definition| definition.operation)
+    }
+
+    pub fn mnemonic(self) -> &'static str {
+        self.definition().mnemonic
+    }
+}
+
+impl Operand {
+    /// Read an operand of a kind, `None` if it is not one.
+    pub fn parse(kind: Kind, text: &str) -> Option<Operand> {
+        let register = || REGISTERS.iter().position(|name| *name == text).map(Operand::Register);
+        let value = || text.parse().ok().map(Operand::Value);
+        match kind {
+            Kind::Value | Kind::Offset => value(),
+            Kind::Register => register(),
+            Kind::Source => register().or_else(value),
+        }
+    }
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operand::Value(value) => write!(f, "{:+}", value),
+            Operand::Register(register) => write!(f, "{}", REGISTERS[*register]),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Instruction {
+    pub operation: Operation,
+    pub operands: [Operand; 2],
+}
+
+impl Instruction {
+    /// An instruction with one number for its operand, as all of day 8.
+    pub fn new(operation: Operation, operand: isize) -> Instruction {
+        Instruction {
+            operation,
+            operands: [Operand::Value(operand), Operand::Value(0)],
+        }
+    }
+
+    pub fn from_line(line: &str) -> Result<Self, ParseError> {
+        let invalid = |reason: &str| ParseError::new(DAY, line, reason);
+
+        let mut split = line.split(' ');
+        let raw_operation = split.next().unwrap_or_default();
+        let operation =
+            Operation::from_mnemonic(raw_operation).ok_or_else(|| invalid("unknown operation").at_column(1))?;
+
+        let mut operands = [Operand::Value(0); 2];
+        let mut column = raw_operation.len() + 2;
+        for (i, &kind) in operation.definition().operands.iter().enumerate() {
+            let raw_operand = split.next().ok_or_else(|| invalid("missing operand"))?;
+            operands[i] =
+                Operand::parse(kind, raw_operand).ok_or_else(|| invalid("invalid operand").at_column(column))?;
+            column += raw_operand.len() + 1;
+        }
+        if split.next().is_some() {
+            return Err(invalid(&too_many(operation)));
+        }
+
+        Ok(Instruction { operation, operands })
+    }
+
+    /// The number of instructions it jumps, or would jump.
+    pub fn offset(&self) -> Option<isize> {
+        let kinds = self.operation.definition().operands;
+        match kinds.iter().position(|&kind| kind == Kind::Offset).map(|i| self.operands[i]) {
+            Some(Operand::Value(offset)) => Some(offset),
+            _ => None,
+        }
+    }
+}
+
+fn too_many(operation: Operation) -> String {
+    match operation.definition().operands.len() {
+        1 => String::from("more than one operand"),
+        n => format!("more than {} operands", n),
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.operation.mnemonic())?;
+        for operand in self.operands.iter().take(self.operation.definition().operands.len()) {
+            write!(f, " {}", operand)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Program {
+    pub instructions: Vec<Instruction>,
+}
+
+impl Program {
+    pub fn add_instruction(&mut self, line: &str) -> Result<(), ParseError> {
+        // Every line holds one instruction
+        let line_nr = self.instructions.len() + 1;
+        self.instructions.push(Instruction::from_line(line).map_err(|e| e.at_line(line_nr))?);
+        Ok(())
+    }
+
+    /// Assemble a program written by hand. Next to the plain instructions of
+    /// the puzzle input, a line can start with a label (`loop:`) that a jump
+    /// or `nop` can use instead of an offset (`jmp loop`), everything after a
+    /// `#` is a comment, and blank lines are skipped. A label after the last
+    /// instruction is where the program ends.
+    pub fn assemble(source: &str) -> Result<Program, ParseError> {
+        // The lines with an instruction, and the instruction every label is at
+        let mut code = vec![];
+        let mut labels: HashMap<&str, usize> = HashMap::new();
+        for (line_nr, line) in (1..).zip(source.lines()) {
+            let invalid = |part: &str, reason| ParseError::new(DAY, line, reason)
+                .at_line(line_nr)
+                .at_column(column(line, part));
+            let mut rest = line.split('#').next().unwrap_or_default().trim();
+            if let Some(colon) = rest.find(':') {
+                let label = rest[..colon].trim_end();
+                if !is_label(label) {
+                    return Err(invalid(label, "invalid label"));
+                }
+                if labels.insert(label, code.len()).is_some() {
+                    return Err(invalid(label, "label defined twice"));
+                }
+                rest = rest[colon + 1..].trim_start();
+            }
+            if !rest.is_empty() {
+                code.push((line_nr, line, rest));
+            }
+        }
+
+        let mut program = Program { instructions: vec![] };
+        for (index, &(line_nr, line, text)) in code.iter().enumerate() {
+            let invalid = |part: &str, reason: &str| ParseError::new(DAY, line, reason)
+                .at_line(line_nr)
+                .at_column(column(line, part));
+            let mut split = text.split_whitespace();
+            let raw_operation = split.next().unwrap_or_default();
+            let operation =
+                Operation::from_mnemonic(raw_operation).ok_or_else(|| invalid(raw_operation, "unknown operation"))?;
+
+            let mut operands = [Operand::Value(0); 2];
+            for (i, &kind) in operation.definition().operands.iter().enumerate() {
+                let raw_operand = split.next().ok_or_else(|| invalid(text, "missing operand"))?;
+                operands[i] = if kind == Kind::Offset && is_label(raw_operand) {
+                    let target = labels.get(raw_operand).ok_or_else(|| invalid(raw_operand, "unknown label"))?;
+                    Operand::Value(*target as isize - index as isize)
+                } else {
+                    Operand::parse(kind, raw_operand).ok_or_else(|| invalid(raw_operand, "invalid operand"))?
+                };
+            }
+            if let Some(extra) = split.next() {
+                return Err(invalid(extra, &too_many(operation)));
+            }
+            program.instructions.push(Instruction { operation, operands });
+        }
+
+        Ok(program)
+    }
+
+    /// A listing that `assemble` turns back into the same program, with a
+    /// label `lN` at every instruction N that a jump or `nop` leads to.
+    pub fn disassemble(&self) -> String {
+        let mut labelled = vec![false; self.instructions.len() + 1];
+        for index in 0..self.instructions.len() {
+            if let Some(target) = self.jump_target(index) {
+                labelled[target] = true;
+            }
+        }
+
+        let mut listing = String::new();
+        for (index, &labelled) in labelled.iter().enumerate() {
+            if labelled {
+                listing.push_str(&format!("l{}:\n", index));
+            }
+            if let Some(instruction) = self.instructions.get(index) {
+                let kinds = instruction.operation.definition().operands;
+                listing.push_str(&format!("    {}", instruction.operation.mnemonic()));
+                for (&kind, operand) in kinds.iter().zip(instruction.operands.iter()) {
+                    match self.jump_target(index) {
+                        Some(target) if kind == Kind::Offset => listing.push_str(&format!(" l{}", target)),
+                        _ => listing.push_str(&format!(" {}", operand)),
+                    }
+                }
+                listing.push('\n');
+            }
+        }
+        listing
+    }
+
+    /// Where the instruction at `index` jumps to, or would if it were a
+    /// jump, if that is in the program or right after its end.
+    pub fn jump_target(&self, index: usize) -> Option<usize> {
+        let target = index as isize + self.instructions.get(index)?.offset()?;
+        if target >= 0 && target as usize <= self.instructions.len() {
+            Some(target as usize)
+        } else {
+            None
+        }
+    }
+
+    /// The registers the program uses, the accumulator always among them.
+    pub fn registers(&self) -> Vec<Register> {
+        let mut registers = vec![ACC];
+        for instruction in self.instructions.iter() {
+            for operand in instruction.operands.iter() {
+                if let Operand::Register(register) = *operand {
+                    registers.push(register);
+                }
+            }
+        }
+        registers.sort_unstable();
+        registers.dedup();
+        registers
+    }
+}
+
+impl Clone for Program {
+    fn clone(&self) -> Program {
+        Program {
+            instructions: self.instructions.clone(),
+        }
+    }
+}
+
+/// Labels are names, so they cannot be mistaken for offsets.
+fn is_label(text: &str) -> bool {
+    let mut chars = text.chars();
+    matches!(chars.next(), Some(ch) if ch.is_ascii_alphabetic() || ch == '_')
+        && chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+}
+
+// Column of a part of a line, counting from 1
+fn column(line: &str, part: &str) -> usize {
+    part.as_ptr() as usize - line.as_ptr() as usize + 1
+}
+
+/// One instruction that ran, with enough to undo it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Step {
+    pub instruction_pointer: usize,
+    pub instruction: Instruction,
+    pub registers_before: [isize; REGISTER_COUNT],
+    pub registers_after: [isize; REGISTER_COUNT],
+    /// The number it read from the input
+    pub input: Option<isize>,
+    /// Whether it wrote a number to the output
+    pub output: bool,
+    /// Whether the instruction had not run before
+    pub first_visit: bool,
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The register the instruction writes to, or else the accumulator
+        let kinds = self.instruction.operation.definition().operands;
+        let written = kinds.iter().position(|&kind| kind == Kind::Register);
+        let register = match written.map(|i| self.instruction.operands[i]) {
+            Some(Operand::Register(register)) => register,
+            _ => ACC,
+        };
+        write!(
+            f,
+            "{:>4}  {}  {} {} -> {}",
+            self.instruction_pointer,
+            self.instruction,
+            REGISTERS[register],
+            self.registers_before[register],
+            self.registers_after[register]
+        )
+    }
+}
+
+#[derive(Debug)]
+pub struct CPU {
+    pub registers: [isize; REGISTER_COUNT],
+    pub instruction_pointer: usize,
+    pub program: Program,
+    pub visited: Vec<bool>,
+    /// Every instruction that ran, in order, when recording
+    pub trace: Vec<Step>,
+    recording: bool,
+    /// Numbers for `inp` to read, first one first
+    pub input: VecDeque<isize>,
+    /// Numbers written by `out`
+    pub output: Vec<isize>,
+}
+
+impl Clone for CPU {
+    fn clone(&self) -> CPU {
+        CPU {
+            registers: self.registers,
+            instruction_pointer: self.instruction_pointer,
+            program: self.program.clone(),
+            visited: self.visited.clone(),
+            trace: self.trace.clone(),
+            recording: self.recording,
+            input: self.input.clone(),
+            output: self.output.clone(),
+        }
+    }
+}
+
+impl CPU {
+    pub fn new(program: Program) -> CPU {
+        let len = program.instructions.len();
+        CPU {
+            registers: [0; REGISTER_COUNT],
+            instruction_pointer: 0,
+            program,
+            visited: vec![false; len],
+            trace: vec![],
+            recording: false,
+            input: VecDeque::new(),
+            output: vec![],
+        }
+    }
+
+    /// Keep a trace of every instruction that runs, to step back and find
+    /// cycles. It grows with every step, so only for when that is needed.
+    pub fn recording(mut self) -> CPU {
+        self.recording = true;
+        self
+    }
+
+    pub fn accumulator(&self) -> isize {
+        self.registers[ACC]
+    }
+
+    /// The number an operand stands for.
+    pub fn get(&self, operand: Operand) -> isize {
+        match operand {
+            Operand::Value(value) => value,
+            Operand::Register(register) => self.registers[register],
+        }
+    }
+
+    fn set(&mut self, operand: Operand, value: isize) {
+        if let Operand::Register(register) = operand {
+            self.registers[register] = value;
+        }
+    }
+
+    fn update_instruction_pointer(&mut self, delta: isize) -> Result<(), Fault> {
+        type IpModifier = fn(usize, usize) -> Option<usize>;
+        let mut f: IpModifier = usize::checked_add;
+        if delta < 0 {
+            f = usize::checked_sub;
+        }
+
+        match f(self.instruction_pointer, delta.unsigned_abs()) {
+            Some(ip) => self.instruction_pointer = ip,
+            None => return Err(Fault::JumpOutOfBounds),
+        }
+
+        Ok(())
+    }
+
+    pub fn visited(&self) -> bool {
+        self.visited[self.instruction_pointer]
+    }
+
+    pub fn terminated(&self) -> bool {
+        self.instruction_pointer >= self.program.instructions.len()
+    }
+
+    pub fn single_step(&mut self) -> Result<(), Fault> {
+        let instruction_pointer = self.instruction_pointer;
+        let registers_before = self.registers;
+        let next_input = self.input.front().copied();
+        let (input_len, output_len) = (self.input.len(), self.output.len());
+        let first_visit = !self.visited[instruction_pointer];
+        let instruction = self.program.instructions[instruction_pointer];
+        let delta = (instruction.operation.definition().execute)(self, instruction.operands)?;
+        self.update_instruction_pointer(delta)?;
+        self.visited[instruction_pointer] = true;
+        if self.recording {
+            self.trace.push(Step {
+                instruction_pointer,
+                instruction,
+                registers_before,
+                registers_after: self.registers,
+                input: if self.input.len() < input_len { next_input } else { None },
+                output: self.output.len() > output_len,
+                first_visit,
+            });
+        }
+        Ok(())
+    }
+
+    /// Undo the last instruction that ran, returning it, or `None` at the
+    /// start of the program or when not recording.
+    pub fn step_back(&mut self) -> Option<Step> {
+        let step = self.trace.pop()?;
+        self.instruction_pointer = step.instruction_pointer;
+        self.registers = step.registers_before;
+        if let Some(input) = step.input {
+            self.input.push_front(input);
+        }
+        if step.output {
+            self.output.pop();
+        }
+        if step.first_visit {
+            self.visited[step.instruction_pointer] = false;
+        }
+        Some(step)
+    }
+
+    /// The instructions that led back to the current one, when it is about
+    /// to run a second time: from its last run up to here. Only when
+    /// recording.
+    pub fn cycle(&self) -> Option<&[Step]> {
+        if self.terminated() || !self.visited() {
+            return None;
+        }
+        let start = self.trace.iter().rposition(|step| step.instruction_pointer == self.instruction_pointer)?;
+        Some(&self.trace[start..])
+    }
+
+    pub fn run_program_until_loop(&mut self) -> Result<bool, Fault> {
+        while !self.visited() {
+            self.single_step()?;
+
+            if self.terminated() {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Instruction, Operand, Operation, Program, CPU};
+
+    static TEST_DATA: &str = "nop +0
+acc +1
+jmp +4
+acc +3
+jmp -3
+acc -99
+acc +1
+jmp -4
+acc +6";
+
+    // Reads n and writes n! once for every number from n down to 1
+    static FACTORIALS: &str = "
+        inp a
+        cpy 1 b
+loop:   jz a done
+        mul b a
+        out b
+        add a -1
+        jmp loop
+done:   acc +1
+";
+
+    #[test]
+    fn test_instruction_set() {
+        for (index, definition) in super::INSTRUCTION_SET.iter().enumerate() {
+            assert_eq!(definition.operation as usize, index, "{} out of order", definition.mnemonic);
+            assert_eq!(Operation::from_mnemonic(definition.mnemonic), Some(definition.operation));
+        }
+    }
+
+    #[test]
+    fn test_from_line() {
+        let instruction = Instruction::from_line("jnz c -2").expect("Invalid instruction");
+        assert_eq!(instruction.operands, [Operand::Register(3), Operand::Value(-2)]);
+        assert_eq!(instruction.offset(), Some(-2));
+        assert_eq!(instruction.to_string(), "jnz c -2");
+        assert_eq!(Instruction::from_line("cpy 7 acc").map(|x| x.to_string()), Ok(String::from("cpy +7 acc")));
+
+        let err = Instruction::from_line("cpy 7 x").expect_err("Unknown register accepted");
+        assert_eq!((err.reason.as_str(), err.column), ("invalid operand", Some(7)));
+        let err = Instruction::from_line("mul a").expect_err("Missing operand accepted");
+        assert_eq!(err.reason, "missing operand");
+        let err = Instruction::from_line("out a b").expect_err("Extra operand accepted");
+        assert_eq!(err.reason, "more than one operand");
+        let err = Instruction::from_line("add a b c").expect_err("Extra operand accepted");
+        assert_eq!(err.reason, "more than 2 operands");
+    }
+
+    #[test]
+    fn test_registers() {
+        let program = Program::assemble(FACTORIALS).expect("Invalid program");
+        assert_eq!(program.registers(), vec![0, 1, 2]);
+        let mut cpu = CPU::new(program).recording();
+        cpu.input.push_back(5);
+        assert_eq!(cpu.run_program_until_loop(), Ok(false));
+        assert_eq!(cpu.output, vec![5]);
+
+        // Without looking at the instructions visited before
+        while !cpu.terminated() {
+            cpu.single_step().expect("Program failed");
+        }
+        assert_eq!(cpu.output, vec![5, 20, 60, 120, 120]);
+        assert_eq!(cpu.registers, [1, 0, 120, 0, 0]);
+
+        // Back to before the input was read
+        while cpu.step_back().is_some() {}
+        assert_eq!((cpu.output.len(), cpu.input.len(), cpu.registers), (0, 1, [0; super::REGISTER_COUNT]));
+        cpu.input.clear();
+        assert_eq!(cpu.single_step(), Err(super::Fault::NoInput));
+        cpu.input.push_back(isize::MAX);
+        assert_eq!(cpu.run_program_until_loop(), Ok(false));
+        cpu.single_step().expect("Program failed");
+        assert_eq!(cpu.single_step(), Err(super::Fault::Overflow));
+    }
+
+    #[test]
+    fn test_trace() {
+        let mut cpu = CPU::new(Program::assemble(TEST_DATA).expect("Invalid test data")).recording();
+        assert_eq!(cpu.run_program_until_loop().ok(), Some(false));
+        assert_eq!(cpu.trace.len(), 7);
+        let cycle: Vec<String> = cpu.cycle().expect("No loop").iter().map(|step| step.to_string()).collect();
+        assert_eq!(
+            cycle,
+            vec![
+                "   1  acc +1  acc 0 -> 1",
+                "   2  jmp +4  acc 1 -> 1",
+                "   6  acc +1  acc 1 -> 2",
+                "   7  jmp -4  acc 2 -> 2",
+                "   3  acc +3  acc 2 -> 5",
+                "   4  jmp -3  acc 5 -> 5",
+            ]
+        );
+
+        // Back to where the loop started, it has not been run yet
+        for _ in 0..6 {
+            cpu.step_back().expect("Cannot step back");
+        }
+        assert_eq!((cpu.instruction_pointer, cpu.accumulator()), (1, 0));
+        assert_eq!(cpu.cycle(), None);
+        assert_eq!(cpu.visited, vec![true, false, false, false, false, false, false, false, false]);
+        cpu.step_back();
+        assert_eq!(cpu.step_back(), None);
+        assert_eq!(cpu.run_program_until_loop().ok(), Some(false));
+        assert_eq!(cpu.accumulator(), 5);
+
+        // Without recording there is nothing to go back to
+        let mut cpu = CPU::new(cpu.program);
+        assert_eq!(cpu.run_program_until_loop().ok(), Some(false));
+        assert_eq!((cpu.trace.len(), cpu.cycle()), (0, None));
+        assert_eq!(cpu.step_back(), None);
+    }
+
+    #[test]
+    fn test_assemble() {
+        let source = "
+# Adds up 3 + 2 + 1, then leaves through the end
+        acc +3
+loop:   acc -1      # one less every time round
+        jmp test
+back:   jmp loop
+
+test:   nop +0
+        jmp back
+        jmp end
+end:
+";
+        let program = Program::assemble(source).expect("Invalid program");
+        let offsets: Vec<Option<isize>> = program.instructions.iter().map(|x| x.offset()).collect();
+        assert_eq!(offsets, vec![None, None, Some(2), Some(-2), Some(0), Some(-2), Some(1)]);
+        assert_eq!(program.instructions[0], Instruction::new(Operation::Acc, 3));
+        assert_eq!(program.instructions[2].operation, Operation::Jmp);
+        assert_eq!(Program::assemble("jmp l2\nacc +1\nl2:"), Program::assemble("jmp +2\nacc +1"));
+
+        let err = Program::assemble("start: nop +0\n  jmp stop\n").expect_err("Unknown label accepted");
+        assert_eq!(err.to_string(), "day 8, line 2, column 7: unknown label: \"  jmp stop\"");
+        let err = Program::assemble("a: nop +0\na: acc +1\n").expect_err("Duplicate label accepted");
+        assert_eq!((err.line, err.column), (Some(2), Some(1)));
+        let err = Program::assemble("  2go: nop +0\n").expect_err("Invalid label accepted");
+        assert_eq!((err.line, err.column), (Some(1), Some(3)));
+        let err = Program::assemble("acc +1 +2 # two\n").expect_err("Two operands accepted");
+        assert_eq!((err.reason.as_str(), err.column), ("more than one operand", Some(8)));
+        let err = Program::assemble("jz acc\n").expect_err("Missing operand accepted");
+        assert_eq!((err.reason.as_str(), err.column), ("missing operand", Some(1)));
+    }
+
+    #[test]
+    fn test_disassemble() {
+        let program = Program::assemble(TEST_DATA).expect("Invalid test data");
+        let listing = program.disassemble();
+        assert!(listing.starts_with("l0:\n    nop l0\nl1:\n    acc +1\n    jmp l6\n"), "{}", listing);
+        assert_eq!(Program::assemble(&listing), Ok(program));
+
+        // Jumps out of the program keep their offset
+        let program = Program::assemble("jmp +5\njmp -2\nacc -7\n").expect("Invalid program");
+        assert_eq!(program.disassemble(), "    jmp +5\n    jmp -2\n    acc -7\n");
+
+        let program = Program::assemble(super::tests::FACTORIALS).expect("Invalid program");
+        let listing = program.disassemble();
+        assert!(listing.contains("l2:\n    jz a l7\n    mul b a\n"), "{}", listing);
+        assert_eq!(Program::assemble(&listing), Ok(program));
+    }
+}
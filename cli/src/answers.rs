@@ -0,0 +1,109 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+/// Known-good answers for the real puzzle inputs, stored as TOML with a
+/// `[dayNN]` table per day holding `part1` and `part2`.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Answers {
+    #[serde(flatten)]
+    days: BTreeMap<String, DayAnswers>,
+}
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+struct DayAnswers {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part1: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part2: Option<String>,
+}
+
+/// Outcome of comparing an answer with the recorded one.
+#[derive(Debug, PartialEq)]
+pub enum Check {
+    Match,
+    Mismatch(String),
+    Unrecorded,
+}
+
+fn key(day: u8) -> String {
+    // Zero-padded so the file is sorted by day
+    format!("day{:02}", day)
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> io::Result<Answers> {
+        let contents = fs::read_to_string(path)?;
+        toml::from_str(&contents).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let contents =
+            toml::to_string(self).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, contents)
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        let answers = self.days.get(&key(day))?;
+        match part {
+            1 => answers.part1.as_deref(),
+            _ => answers.part2.as_deref(),
+        }
+    }
+
+    pub fn set(&mut self, day: u8, part: u8, answer: &str) {
+        let answers = self.days.entry(key(day)).or_default();
+        match part {
+            1 => answers.part1 = Some(answer.to_string()),
+            _ => answers.part2 = Some(answer.to_string()),
+        }
+    }
+
+    pub fn check(&self, day: u8, part: u8, answer: Option<&str>) -> Check {
+        match self.get(day, part) {
+            None => Check::Unrecorded,
+            Some(expected) if Some(expected) == answer => Check::Match,
+            Some(expected) => Check::Mismatch(expected.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Answers, Check};
+
+    static TEST_DATA: &str = "[day08]
+part1 = \"5\"
+part2 = \"8\"
+
+[day25]
+part1 = \"14897079\"
+";
+
+    #[test]
+    fn test_check() {
+        let answers: Answers = toml::from_str(TEST_DATA).expect("Invalid test data");
+
+        assert_eq!(answers.check(8, 1, Some("5")), Check::Match);
+        assert_eq!(answers.check(8, 2, Some("7")), Check::Mismatch(String::from("8")));
+        assert_eq!(answers.check(8, 2, None), Check::Mismatch(String::from("8")));
+        assert_eq!(answers.check(25, 2, None), Check::Unrecorded);
+        assert_eq!(answers.check(1, 1, Some("514579")), Check::Unrecorded);
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let mut answers = Answers::default();
+        answers.set(25, 1, "14897079");
+        answers.set(8, 2, "8");
+        answers.set(8, 1, "5");
+
+        let written = toml::to_string(&answers).expect("Could not serialize answers");
+        assert_eq!(written, TEST_DATA);
+        let read: Answers = toml::from_str(&written).expect("Could not deserialize answers");
+        assert_eq!(read, answers);
+    }
+}
@@ -0,0 +1,549 @@
+use std::collections::BTreeSet;
+use std::io::{self, BufRead, Write};
+
+use aoc::vm::{Branch, Fault, Program, Register, ACC, CPU, REGISTERS};
+
+static HELP: &str = "\
+step [N]        run one instruction, or N
+back [N]        undo the last instruction that ran, or the last N
+continue        run until a breakpoint, a watch, a loop or the end
+cycle           show the instructions that led back to the current one
+break IP        stop before running the instruction at IP
+watch REG [N]   stop when register REG (e.g. acc) changes, or passes N
+delete [IP]     remove the breakpoint at IP, or all breakpoints and watches
+regs            show the registers, input, output, breakpoints and watches
+list [IP]       show the instructions around IP (default: the current one)
+visited         show which instructions have run
+reset           start the program again
+input N...      add numbers for inp to read
+quit            stop debugging
+An empty line repeats the last command. Commands can be shortened as long
+as only one starts that way, e.g. br for break, ba for back and res for
+reset; s and c are short for step and continue.";
+
+static COMMANDS: [&str; 14] = [
+    "step", "back", "continue", "cycle", "break", "watch", "delete", "regs", "list", "visited", "reset", "input", "quit",
+    "help",
+];
+
+static ALIASES: [(&str, &str); 2] = [("s", "step"), ("c", "continue")];
+
+/// The command a name stands for: the one it is short for, if no other
+/// command starts with it as well.
+fn command_name(name: &str) -> Result<&'static str, String> {
+    let alias = ALIASES.iter().find(|(alias, _)| *alias == name).map(|&(_, full)| full);
+    if let Some(full) = alias.or_else(|| COMMANDS.iter().copied().find(|full| *full == name)) {
+        return Ok(full);
+    }
+    let matches: Vec<&'static str> = COMMANDS
+        .iter()
+        .copied()
+        .filter(|full| !name.is_empty() && full.starts_with(name))
+        .collect();
+    match matches[..] {
+        [full] => Ok(full),
+        [] => Err(format!("Unknown command {}, try help", name)),
+        _ => Err(format!("Ambiguous command: {} ({})", name, matches.join(", "))),
+    }
+}
+
+/// When to stop because of a register.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Watch {
+    Change(Register),
+    /// Going from below the value to at or above it, or the other way round
+    Cross(Register, isize),
+}
+
+/// Why running stopped.
+#[derive(Debug, PartialEq)]
+enum Stop {
+    Done,
+    Breakpoint,
+    Watch(Watch, isize),
+    Loop,
+    Terminated,
+    Fault(Fault),
+}
+
+/// The handheld of day 8 running a program under control of the user.
+pub struct Debugger {
+    program: Program,
+    cpu: CPU,
+    /// What `inp` reads, also after a reset
+    input: Vec<isize>,
+    breakpoints: BTreeSet<usize>,
+    watches: Vec<Watch>,
+    /// Whether running an instruction again means the program loops for
+    /// ever, as it does without conditional jumps
+    loops_on_revisit: bool,
+    fault: Option<Fault>,
+}
+
+impl Debugger {
+    pub fn new(program: Program, input: Vec<isize>) -> Debugger {
+        let mut cpu = CPU::new(program.clone()).recording();
+        cpu.input.extend(input.iter());
+        let loops_on_revisit = program
+            .instructions
+            .iter()
+            .all(|instruction| instruction.operation.definition().branch != Branch::Conditional);
+        Debugger {
+            cpu,
+            program,
+            input,
+            breakpoints: BTreeSet::new(),
+            watches: vec![],
+            loops_on_revisit,
+            fault: None,
+        }
+    }
+
+    fn finished(&self) -> bool {
+        self.cpu.terminated() || self.fault.is_some()
+    }
+
+    /// Run one instruction, and say why to stop if it gives a reason.
+    fn step(&mut self) -> Option<Stop> {
+        let before = self.cpu.registers;
+        if let Err(fault) = self.cpu.single_step() {
+            self.fault = Some(fault);
+            return Some(Stop::Fault(fault));
+        }
+        if self.cpu.terminated() {
+            return Some(Stop::Terminated);
+        }
+
+        let after = self.cpu.registers;
+        self.watches
+            .iter()
+            .find(|watch| match **watch {
+                Watch::Change(register) => before[register] != after[register],
+                Watch::Cross(register, value) => (before[register] < value) != (after[register] < value),
+            })
+            .map(|&watch| Stop::Watch(watch, before[register(watch)]))
+    }
+
+    fn run(&mut self, steps: Option<usize>) -> Stop {
+        let mut done = 0;
+        loop {
+            if let Some(stop) = self.step() {
+                return stop;
+            }
+            done += 1;
+            match steps {
+                Some(steps) if done == steps => return Stop::Done,
+                Some(_) => (),
+                None if self.breakpoints.contains(&self.cpu.instruction_pointer) => return Stop::Breakpoint,
+                None if self.loops_on_revisit && self.cpu.visited() => return Stop::Loop,
+                None => (),
+            }
+        }
+    }
+
+    fn report(&self, stop: Stop) -> String {
+        let ip = self.cpu.instruction_pointer;
+        let acc = self.cpu.accumulator();
+        let reason = match stop {
+            Stop::Done | Stop::Breakpoint | Stop::Loop => None,
+            Stop::Watch(watch, before) => {
+                let after = self.cpu.registers[register(watch)];
+                Some(match watch {
+                    Watch::Change(register) => format!("{} changed from {} to {}", name(register), before, after),
+                    Watch::Cross(register, value) => {
+                        format!("{} passed {}, from {} to {}", name(register), value, before, after)
+                    }
+                })
+            }
+            Stop::Terminated => return format!("Program terminated, accumulator {}", acc),
+            Stop::Fault(fault) => return format!("Instruction {} {}, accumulator {}", ip, fault, acc),
+        };
+        let mut report = reason.map(|reason| format!("{}\n", reason)).unwrap_or_default();
+        if stop == Stop::Breakpoint {
+            report.push_str(&format!("Breakpoint at {}\n", ip));
+        }
+        if stop == Stop::Loop {
+            report.push_str(&format!("Instruction {} is about to run a second time\n", ip));
+        }
+        report.push_str(&self.line(ip));
+        report
+    }
+
+    /// One instruction as listed: the current one marked with `=>` and
+    /// those with a breakpoint with `*`.
+    fn line(&self, ip: usize) -> String {
+        let current = if ip == self.cpu.instruction_pointer { "=>" } else { "  " };
+        let breakpoint = if self.breakpoints.contains(&ip) { '*' } else { ' ' };
+        let instruction = match self.program.instructions.get(ip) {
+            Some(instruction) => instruction,
+            None => return format!("{}{}{:>4}  end of program", current, breakpoint, ip),
+        };
+        let mut line = format!("{}{}{:>4}  {}", current, breakpoint, ip, instruction);
+        match self.program.jump_target(ip) {
+            Some(target) if instruction.operation.definition().branch != Branch::Never => {
+                line.push_str(&format!("  -> {}", target))
+            }
+            _ => (),
+        }
+        line
+    }
+
+    fn list(&self, around: usize) -> String {
+        let from = around.saturating_sub(3);
+        let to = around.saturating_add(4).min(self.program.instructions.len());
+        (from..to).map(|ip| self.line(ip)).collect::<Vec<String>>().join("\n")
+    }
+
+    fn regs(&self) -> String {
+        let registers: Vec<String> = self
+            .program
+            .registers()
+            .into_iter()
+            .map(|register| format!("{} {}", REGISTERS[register], self.cpu.registers[register]))
+            .collect();
+        let mut regs = format!(
+            "ip {}, {}, {} instructions run",
+            self.cpu.instruction_pointer,
+            registers.join(", "),
+            self.cpu.trace.len()
+        );
+        let input: Vec<isize> = self.cpu.input.iter().copied().collect();
+        for (name, numbers) in [("input", &input), ("output", &self.cpu.output)] {
+            let numbers: Vec<String> = numbers.iter().map(|number| number.to_string()).collect();
+            if !numbers.is_empty() {
+                regs.push_str(&format!("\n{} {}", name, numbers.join(", ")));
+            }
+        }
+        if !self.breakpoints.is_empty() {
+            let breakpoints: Vec<String> = self.breakpoints.iter().map(|ip| ip.to_string()).collect();
+            regs.push_str(&format!("\nbreakpoints at {}", breakpoints.join(", ")));
+        }
+        for watch in self.watches.iter() {
+            match watch {
+                Watch::Change(register) => regs.push_str(&format!("\nwatching {}", REGISTERS[*register])),
+                Watch::Cross(register, value) => {
+                    regs.push_str(&format!("\nwatching {} passing {}", REGISTERS[*register], value))
+                }
+            }
+        }
+        regs
+    }
+
+    /// Every instruction as `#` when it has run and `.` when not, 50 on
+    /// every row.
+    fn visited(&self) -> String {
+        let rows: Vec<String> = self
+            .cpu
+            .visited
+            .chunks(50)
+            .enumerate()
+            .map(|(row, chunk)| {
+                let cells: String = chunk.iter().map(|&visited| if visited { '#' } else { '.' }).collect();
+                format!("{:>5}  {}", row * 50, cells)
+            })
+            .collect();
+        rows.join("\n")
+    }
+
+    /// Carry out one command, returning what to show, or `None` to quit.
+    pub fn execute(&mut self, command: &str) -> Option<String> {
+        let mut words = command.split_whitespace();
+        let name = words.next().unwrap_or_default();
+        let args: Vec<&str> = words.collect();
+        let name = match command_name(name) {
+            Ok(name) => name,
+            Err(e) => return Some(e),
+        };
+        let is = |full: &str| full == name;
+        let number =
+            |arg: Option<&&str>| arg.map(|arg| arg.parse::<usize>().map_err(|_| format!("Not a number: {}", arg)));
+        let len = self.program.instructions.len();
+
+        let reply = if is("quit") {
+            return None;
+        } else if (is("step") || is("continue")) && self.finished() {
+            String::from("The program has finished, reset to run it again")
+        } else if is("step") {
+            match number(args.first()).unwrap_or(Ok(1)) {
+                Ok(0) => String::from("Nothing to run"),
+                Ok(steps) => {
+                    let stop = self.run(Some(steps));
+                    self.report(stop)
+                }
+                Err(e) => e,
+            }
+        } else if is("continue") {
+            let stop = self.run(None);
+            self.report(stop)
+        } else if is("cycle") {
+            match self.cpu.cycle() {
+                Some(cycle) => {
+                    let steps: Vec<String> = cycle.iter().map(|step| format!("   {}", step)).collect();
+                    format!("{}\n{}", steps.join("\n"), self.line(self.cpu.instruction_pointer))
+                }
+                None => String::from("Not in a loop"),
+            }
+        } else if is("break") {
+            match number(args.first()) {
+                Some(Ok(ip)) if ip < len => {
+                    self.breakpoints.insert(ip);
+                    format!("Breakpoint at {}", ip)
+                }
+                Some(Ok(ip)) => format!("There is no instruction {}", ip),
+                Some(Err(e)) => e,
+                None => String::from("Break where?"),
+            }
+        } else if is("back") {
+            match number(args.first()).unwrap_or(Ok(1)) {
+                Ok(steps) => {
+                    // An instruction that faulted never ran, going back to it is the first step
+                    let faulted = usize::from(self.fault.take().is_some());
+                    let undone = faulted + (faulted..steps).take_while(|_| self.cpu.step_back().is_some()).count();
+                    let start = if undone < steps { "At the start of the program\n" } else { "" };
+                    format!("{}{}", start, self.line(self.cpu.instruction_pointer))
+                }
+                Err(e) => e,
+            }
+        } else if is("watch") {
+            let register = args.first().and_then(|arg| REGISTERS.iter().position(|name| name == arg));
+            match (register, args.get(1)) {
+                (None, _) => format!("Watch which register? One of {}", REGISTERS.join(", ")),
+                (Some(register), None) => {
+                    self.watches.push(Watch::Change(register));
+                    format!("Watching {}", REGISTERS[register])
+                }
+                (Some(register), Some(value)) => match value.parse() {
+                    Ok(value) => {
+                        self.watches.push(Watch::Cross(register, value));
+                        format!("Watching {} passing {}", REGISTERS[register], value)
+                    }
+                    Err(_) => format!("Not a number: {}", value),
+                },
+            }
+        } else if is("delete") {
+            match number(args.first()) {
+                Some(Ok(ip)) if self.breakpoints.remove(&ip) => format!("Deleted breakpoint at {}", ip),
+                Some(Ok(ip)) => format!("No breakpoint at {}", ip),
+                Some(Err(e)) => e,
+                None => {
+                    self.breakpoints.clear();
+                    self.watches.clear();
+                    String::from("Deleted all breakpoints and watches")
+                }
+            }
+        } else if is("regs") {
+            self.regs()
+        } else if is("list") {
+            match number(args.first()) {
+                Some(Ok(ip)) => self.list(ip),
+                Some(Err(e)) => e,
+                None => self.list(self.cpu.instruction_pointer),
+            }
+        } else if is("visited") {
+            self.visited()
+        } else if is("reset") {
+            self.cpu = CPU::new(self.program.clone()).recording();
+            self.cpu.input.extend(self.input.iter());
+            self.fault = None;
+            self.list(0)
+        } else if is("input") {
+            match args.iter().map(|arg| arg.parse::<isize>()).collect::<Result<Vec<isize>, _>>() {
+                Ok(numbers) if !numbers.is_empty() => {
+                    self.cpu.input.extend(numbers.iter());
+                    // Reading the input may not fail any more
+                    if self.fault == Some(Fault::NoInput) {
+                        self.fault = None;
+                    }
+                    format!("{} numbers to read", self.cpu.input.len())
+                }
+                Ok(_) => String::from("Input what?"),
+                Err(e) => format!("Not a number: {}", e),
+            }
+        } else {
+            String::from(HELP)
+        };
+        Some(reply)
+    }
+}
+
+fn register(watch: Watch) -> Register {
+    match watch {
+        Watch::Change(register) | Watch::Cross(register, _) => register,
+    }
+}
+
+// How a register is named at the start of a sentence
+fn name(register: Register) -> String {
+    match register {
+        ACC => String::from("Accumulator"),
+        _ => format!("Register {}", REGISTERS[register]),
+    }
+}
+
+/// Read commands until `quit` or the end of the input, and show what they
+/// do.
+pub fn repl(debugger: &mut Debugger, input: &mut dyn BufRead, output: &mut dyn Write) -> io::Result<()> {
+    writeln!(output, "{}", debugger.list(0))?;
+    let mut last = String::new();
+    loop {
+        write!(output, "(day8) ")?;
+        output.flush()?;
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            writeln!(output)?;
+            return Ok(());
+        }
+        if !line.trim().is_empty() {
+            last = line.trim().to_string();
+        }
+        match debugger.execute(&last) {
+            Some(reply) => writeln!(output, "{}", reply)?,
+            None => return Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc::vm::{Operation, Program};
+
+    use super::Debugger;
+
+    static TEST_DATA: &str = "nop +0
+acc +1
+jmp +4
+acc +3
+jmp -3
+acc -99
+acc +1
+jmp -4
+acc +6";
+
+    fn debugger() -> Debugger {
+        Debugger::new(Program::assemble(TEST_DATA).expect("Invalid test data"), vec![])
+    }
+
+    #[test]
+    fn test_step() {
+        let mut debugger = debugger();
+        assert_eq!(debugger.execute("step"), Some(String::from("=>    1  acc +1")));
+        assert_eq!(debugger.execute("s 2"), Some(String::from("=>    6  acc +1")));
+        assert_eq!(debugger.execute("regs").unwrap(), "ip 6, acc 1, 3 instructions run");
+        assert_eq!(debugger.execute("visited").unwrap(), "    0  ###......");
+        assert_eq!(debugger.execute("s x").unwrap(), "Not a number: x");
+        assert_eq!(debugger.execute("re").unwrap(), "Ambiguous command: re (regs, reset)");
+        assert_eq!(debugger.execute("b 2").unwrap(), "Ambiguous command: b (back, break)");
+        assert_eq!(debugger.execute("cy").unwrap(), "Not in a loop");
+        assert_eq!(debugger.execute("q"), None);
+    }
+
+    #[test]
+    fn test_continue() {
+        let mut debugger = debugger();
+        assert_eq!(debugger.execute("break 4").unwrap(), "Breakpoint at 4");
+        assert_eq!(debugger.execute("c").unwrap(), "Breakpoint at 4\n=>*   4  jmp -3  -> 1");
+        assert_eq!(debugger.execute("c").unwrap(), "Instruction 1 is about to run a second time\n=>    1  acc +1");
+        assert_eq!(debugger.execute("regs").unwrap(), "ip 1, acc 5, 7 instructions run\nbreakpoints at 4");
+
+        assert_eq!(debugger.execute("delete").unwrap(), "Deleted all breakpoints and watches");
+        debugger.execute("reset");
+        assert_eq!(debugger.execute("watch acc 4").unwrap(), "Watching acc passing 4");
+        assert_eq!(debugger.execute("c").unwrap(), "Accumulator passed 4, from 2 to 5\n=>    4  jmp -3  -> 1");
+        debugger.execute("delete");
+        debugger.execute("watch acc");
+        assert_eq!(debugger.execute("c").unwrap(), "Instruction 1 is about to run a second time\n=>    1  acc +1");
+        assert_eq!(debugger.execute("c").unwrap(), "Accumulator changed from 5 to 6\n=>    2  jmp +4  -> 6");
+    }
+
+    #[test]
+    fn test_back() {
+        let mut debugger = debugger();
+        assert_eq!(debugger.execute("cycle").unwrap(), "Not in a loop");
+        debugger.execute("continue");
+        assert_eq!(
+            debugger.execute("cycle").unwrap(),
+            "      1  acc +1  acc 0 -> 1
+      2  jmp +4  acc 1 -> 1
+      6  acc +1  acc 1 -> 2
+      7  jmp -4  acc 2 -> 2
+      3  acc +3  acc 2 -> 5
+      4  jmp -3  acc 5 -> 5
+=>    1  acc +1"
+        );
+
+        assert_eq!(debugger.execute("back 2").unwrap(), "=>    3  acc +3");
+        assert_eq!(debugger.execute("regs").unwrap(), "ip 3, acc 2, 5 instructions run");
+        assert_eq!(debugger.execute("ba").unwrap(), "=>    7  jmp -4  -> 3");
+        assert_eq!(debugger.execute("back 10").unwrap(), "At the start of the program\n=>    0  nop +0");
+        assert_eq!(debugger.execute("visited").unwrap(), "    0  .........");
+    }
+
+    #[test]
+    fn test_end() {
+        // The fixed program runs to its end
+        let mut program = Program::assemble(TEST_DATA).expect("Invalid test data");
+        program.instructions[7].operation = Operation::Nop;
+        let mut debugger = Debugger::new(program, vec![]);
+        assert_eq!(debugger.execute("c").unwrap(), "Program terminated, accumulator 8");
+        assert_eq!(debugger.execute("s").unwrap(), "The program has finished, reset to run it again");
+
+        let mut debugger = Debugger::new(Program::assemble("acc +2\njmp -2").expect("Invalid program"), vec![]);
+        assert_eq!(debugger.execute("c").unwrap(), "Instruction 1 jumps out of the program, accumulator 2");
+        assert_eq!(debugger.execute("ba").unwrap(), "=>    1  jmp -2");
+        assert_eq!(debugger.execute("s").unwrap(), "Instruction 1 jumps out of the program, accumulator 2");
+        assert_eq!(debugger.execute("back 2").unwrap(), "=>    0  acc +2");
+
+        // Past the last instruction, and in a program without any
+        let mut debugger = Debugger::new(Program::assemble("nop +0\nacc +1").expect("Invalid program"), vec![]);
+        assert_eq!(debugger.execute("c").unwrap(), "Program terminated, accumulator 1");
+        assert_eq!(debugger.execute("back 0").unwrap(), "=>    2  end of program");
+        assert_eq!(debugger.execute(&format!("list {}", usize::MAX)).unwrap(), "");
+        let mut debugger = Debugger::new(Program::assemble("").expect("Invalid program"), vec![]);
+        assert_eq!(debugger.execute("back").unwrap(), "At the start of the program\n=>    0  end of program");
+    }
+
+    #[test]
+    fn test_registers() {
+        // Adds up and writes 3 + 2 + 1
+        let source = "inp a\nloop: add b a\nout b\nadd a -1\njnz a loop\n";
+        let program = Program::assemble(source).expect("Invalid program");
+        let mut debugger = Debugger::new(program.clone(), vec![3]);
+        assert_eq!(debugger.execute("watch b 5").unwrap(), "Watching b passing 5");
+        assert_eq!(debugger.execute("watch x").unwrap(), "Watch which register? One of acc, a, b, c, d");
+        // Running the loop again is no reason to stop
+        assert_eq!(debugger.execute("c").unwrap(), "Register b passed 5, from 3 to 5\n=>    2  out b");
+        assert_eq!(debugger.execute("s 2").unwrap(), "=>    4  jnz a -3  -> 1");
+        assert_eq!(
+            debugger.execute("regs").unwrap(),
+            "ip 4, acc 0, a 1, b 5, 8 instructions run\noutput 3, 5\nwatching b passing 5"
+        );
+        assert_eq!(debugger.execute("c").unwrap(), "Program terminated, accumulator 0");
+        debugger.execute("reset");
+        assert_eq!(debugger.execute("regs").unwrap().lines().nth(1), Some("input 3"));
+
+        let mut debugger = Debugger::new(program, vec![]);
+        assert_eq!(debugger.execute("c").unwrap(), "Instruction 0 reads past the end of the input, accumulator 0");
+        assert_eq!(debugger.execute("s").unwrap(), "The program has finished, reset to run it again");
+        assert_eq!(debugger.execute("input 1 x").unwrap(), "Not a number: invalid digit found in string");
+        assert_eq!(debugger.execute("input 1").unwrap(), "1 numbers to read");
+        assert_eq!(debugger.execute("c").unwrap(), "Program terminated, accumulator 0");
+    }
+
+    #[test]
+    fn test_repl() {
+        let mut debugger = debugger();
+        let mut output = vec![];
+        super::repl(&mut debugger, &mut "br 7\ncontinue\n\nlist 0\nfoo\nq\nregs\n".as_bytes(), &mut output)
+            .expect("Cannot write");
+        let output = String::from_utf8(output).expect("Invalid output");
+        let replies: Vec<&str> = output.split("(day8) ").collect();
+        assert_eq!(replies.len(), 7);
+        assert!(replies[0].starts_with("=>    0  nop +0\n      1  acc +1\n"));
+        assert_eq!(replies[2], "Breakpoint at 7\n=>*   7  jmp -4  -> 3\n");
+        // The empty line continues once more
+        assert_eq!(replies[3], "Instruction 1 is about to run a second time\n=>    1  acc +1\n");
+        assert!(replies[4].starts_with("      0  nop +0\n=>    1  acc +1\n"));
+        assert_eq!(replies[5], "Unknown command foo, try help\n");
+        assert_eq!(replies[6], "");
+    }
+}
@@ -0,0 +1,175 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The example of one part of a puzzle: the input shown in the description
+/// and the answer it has, if the page gives one.
+#[derive(Debug, PartialEq)]
+pub struct PartExample {
+    pub input: String,
+    pub answer: Option<String>,
+}
+
+/// The examples of every part in a puzzle page saved from the site. Each
+/// part is an `<article>`; its example is the first `<pre><code>` block in
+/// it, or that of the part before when it has none (part two often reuses
+/// the example of part one). The answer is the last highlighted
+/// `<code><em>` outside the blocks, which is how the puzzles show the
+/// result of the example.
+pub fn extract(html: &str) -> Vec<PartExample> {
+    let mut articles = between(html, "<article", "</article>");
+    if articles.is_empty() {
+        articles.push(html);
+    }
+
+    let mut examples: Vec<PartExample> = vec![];
+    for article in articles {
+        let blocks = between(article, "<pre><code>", "</code></pre>");
+        let input = match blocks.first() {
+            Some(block) => text(block),
+            None => match examples.last() {
+                Some(example) => example.input.clone(),
+                None => continue,
+            },
+        };
+
+        // Cut out the blocks where they are, their text can show up in the prose as well
+        let mut prose = String::new();
+        let mut rest = 0;
+        for block in blocks {
+            let from = block.as_ptr() as usize - article.as_ptr() as usize;
+            prose.push_str(&article[rest..from]);
+            rest = from + block.len();
+        }
+        prose.push_str(&article[rest..]);
+        let answer = [("<code><em>", "</em></code>"), ("<em><code>", "</code></em>")]
+            .iter()
+            .filter_map(|(start, end)| {
+                let from = prose.rfind(start)?;
+                between(&prose[from..], start, end).first().map(|answer| (from, text(answer)))
+            })
+            .max()
+            .map(|(_, answer)| answer);
+        examples.push(PartExample { input, answer });
+    }
+    examples
+}
+
+/// The text between every `start` and the `end` after it.
+fn between<'a>(text: &'a str, start: &str, end: &str) -> Vec<&'a str> {
+    let mut found = vec![];
+    let mut rest = text;
+    while let Some(from) = rest.find(start) {
+        rest = &rest[from + start.len()..];
+        match rest.find(end) {
+            Some(to) => {
+                found.push(&rest[..to]);
+                rest = &rest[to + end.len()..];
+            }
+            None => break,
+        }
+    }
+    found
+}
+
+/// Plain text from HTML: without tags and with the entities the puzzles use
+/// decoded.
+fn text(html: &str) -> String {
+    let mut plain = String::new();
+    let mut in_tag = false;
+    for ch in html.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => plain.push(ch),
+            _ => (),
+        }
+    }
+    // &amp; last, so that an escaped entity stays as written
+    plain
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Where the examples of a day are kept, next to its input.
+pub fn directory(root: &Path, day: u8) -> PathBuf {
+    root.join(format!("day{}", day)).join("examples")
+}
+
+/// Store the examples as `partN.txt` with the input and `partN.answer` with
+/// the answer, and return the files written.
+pub fn write(root: &Path, day: u8, examples: &[PartExample]) -> Result<Vec<PathBuf>, String> {
+    let dir = directory(root, day);
+    fs::create_dir_all(&dir).map_err(|e| format!("Cannot create {}: {}", dir.display(), e))?;
+    let mut written = vec![];
+    for (part, example) in (1..=2).zip(examples.iter()) {
+        let mut files = vec![(dir.join(format!("part{}.txt", part)), &example.input)];
+        if let Some(answer) = &example.answer {
+            files.push((dir.join(format!("part{}.answer", part)), answer));
+        }
+        for (path, contents) in files {
+            let contents = format!("{}\n", contents.trim_end());
+            fs::write(&path, contents).map_err(|e| format!("Cannot write {}: {}", path.display(), e))?;
+            written.push(path);
+        }
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PartExample;
+
+    static PAGE: &str = r#"<!DOCTYPE html>
+<html><body><main>
+<article class="day-desc"><h2>--- Day 8: Test ---</h2>
+<p>The program looks like this:</p>
+<pre><code>nop +0
+acc +1
+jmp <em>+4</em>
+</code></pre>
+<p>The <code><em>jmp</em></code> at <code>x &lt; 3</code> loops &amp; the accumulator is <code><em>5</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>1337</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>This time the accumulator ends up at <em><code>8</code></em>.</p>
+</article>
+</main></body></html>
+"#;
+
+    #[test]
+    fn test_extract() {
+        let input = String::from("nop +0\nacc +1\njmp +4\n");
+        assert_eq!(
+            super::extract(PAGE),
+            vec![
+                PartExample { input: input.clone(), answer: Some(String::from("5")) },
+                PartExample { input, answer: Some(String::from("8")) },
+            ]
+        );
+
+        // Saved before part one was solved, without any articles
+        let page = "<p>Example:</p><pre><code>a &amp; b\n&lt;c&gt;\n</code></pre>\n<p>Gives <code><em>2</em></code>.</p>";
+        assert_eq!(
+            super::extract(page),
+            vec![PartExample { input: String::from("a & b\n<c>\n"), answer: Some(String::from("2")) }]
+        );
+
+        assert_eq!(super::extract("<p>No examples</p>"), vec![]);
+
+        // The answer comes first and is the same as the example
+        let page = "<article><p>It gives <code><em>7</em></code> for</p><pre><code>7</code></pre></article>";
+        assert_eq!(
+            super::extract(page),
+            vec![PartExample { input: String::from("7"), answer: Some(String::from("7")) }]
+        );
+    }
+
+    #[test]
+    fn test_text() {
+        assert_eq!(super::text("<em>a</em> &amp;lt; b"), "a &lt; b");
+    }
+}
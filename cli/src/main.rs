@@ -0,0 +1,665 @@
+use std::fs;
+use std::io::{self, BufRead, Read};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use aoc::registry;
+use aoc::{vm, Solver};
+
+use answers::{Answers, Check};
+use debug::Debugger;
+use report::Record;
+use scaffold::Example;
+use timing::{measure, millis, Timing};
+
+mod answers;
+mod debug;
+mod examples;
+mod report;
+mod scaffold;
+mod timing;
+
+/// Advent of Code 2020 runner
+#[derive(Parser)]
+#[command(name = "aoc")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+
+    /// Log more on stderr: -v for info, -vv for debug and -vvv for trace.
+    /// RUST_LOG takes precedence, e.g. RUST_LOG=aoc::day22=trace
+    #[arg(short, long, action = clap::ArgAction::Count, global = true)]
+    verbose: u8,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve one day, or all of them
+    Run(RunArgs),
+    /// Start a new day from the templates in template/
+    New(NewArgs),
+    /// Generate a random input for a day, with the answers it is known to have
+    Gen(GenArgs),
+    /// Save the examples and their answers from a puzzle page saved from the site
+    Examples(ExamplesArgs),
+    /// Step through a program of the handheld of day 8, e.g. aoc debug day8 day8/input
+    Debug(DebugArgs),
+}
+
+#[derive(Args)]
+struct RunArgs {
+    /// Day to solve
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25), required_unless_present = "all")]
+    day: Option<u8>,
+
+    /// Only solve this part (default: both)
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// Input file, or - for stdin (default: dayN/input)
+    #[arg(short, long, conflicts_with = "all")]
+    input: Option<PathBuf>,
+
+    /// Solve every day in sequence, each with its dayN/input
+    #[arg(short, long, conflicts_with = "day")]
+    all: bool,
+
+    /// Store the answers as the known-good ones
+    #[arg(long, conflicts_with = "verify")]
+    record: bool,
+
+    /// Check the answers against the known-good ones
+    #[arg(long)]
+    verify: bool,
+
+    /// File with known-good answers
+    #[arg(long, default_value = "answers.toml")]
+    answers: PathBuf,
+
+    /// Time parsing and solving, and print a summary table
+    #[arg(short, long)]
+    time: bool,
+
+    /// Number of runs to time, reporting the fastest and the median
+    #[arg(short = 'n', long, default_value_t = 5, requires = "time")]
+    iterations: usize,
+
+    /// Solve with this implementation of the part instead of the default one
+    #[arg(long, requires = "part", conflicts_with = "all")]
+    variant: Option<String>,
+
+    /// Run every implementation of the selected parts and check they agree
+    #[arg(long, conflicts_with_all = ["variant", "record", "verify", "time", "format"])]
+    cross_check: bool,
+
+    /// Seconds to wait for each implementation when cross-checking
+    #[arg(long, default_value_t = 10, requires = "cross_check")]
+    timeout: u64,
+
+    /// How to print the results
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// Solve the days at the same time, on as many threads as RAYON_NUM_THREADS
+    /// (default: one per core); the results are still printed in order
+    #[cfg(feature = "parallel")]
+    #[arg(long, requires = "all", conflicts_with = "cross_check")]
+    parallel: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum Format {
+    /// Readable text, with a summary table when timing
+    Text,
+    /// One JSON record per solved part on every line
+    Json,
+}
+
+#[derive(Args)]
+struct NewArgs {
+    /// Day to create
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+
+    /// File with the example input from the puzzle, used in the tests
+    #[arg(short, long)]
+    example: Option<PathBuf>,
+
+    /// Answer to the first part for the example
+    #[arg(long)]
+    answer1: Option<String>,
+
+    /// Answer to the second part for the example
+    #[arg(long)]
+    answer2: Option<String>,
+
+    /// Workspace to add the day to
+    #[arg(long, default_value = ".")]
+    root: PathBuf,
+}
+
+#[derive(Args)]
+struct GenArgs {
+    /// Day to generate an input for
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+
+    /// Seed for the random numbers; the same seed gives the same input
+    #[arg(short, long, default_value_t = 0)]
+    seed: u64,
+
+    /// How big to make the input, e.g. the number of lines (meaning differs per day)
+    #[arg(short = 'k', long, default_value_t = 10)]
+    size: usize,
+
+    /// Write the input to this file instead of stdout
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct ExamplesArgs {
+    /// Day the page describes
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+
+    /// The puzzle page, as saved by the browser
+    #[arg(long)]
+    page: PathBuf,
+
+    /// Workspace with the day, the examples go into dayN/examples/
+    #[arg(long, default_value = ".")]
+    root: PathBuf,
+}
+
+#[derive(Args)]
+struct DebugArgs {
+    /// Day with the program, only day8 has one
+    day: String,
+
+    /// The program: a puzzle input, or a listing with labels and comments (default: dayN/input)
+    input: Option<PathBuf>,
+
+    /// Numbers for inp instructions to read, e.g. --feed 3,-1,4
+    #[arg(long, value_delimiter = ',', allow_hyphen_values = true)]
+    feed: Vec<isize>,
+}
+
+struct PartResult {
+    part: u8,
+    variant: &'static str,
+    answer: Option<String>,
+    timing: Timing,
+}
+
+struct DayResult {
+    day: u8,
+    parse: Timing,
+    parts: Vec<PartResult>,
+}
+
+fn default_input(day: u8) -> PathBuf {
+    Path::new(&format!("day{}", day)).join("input")
+}
+
+fn read_input(path: &Path) -> io::Result<String> {
+    if path == Path::new("-") {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input)?;
+        Ok(input)
+    } else {
+        fs::read_to_string(path)
+    }
+}
+
+fn read_day_input(solver: &dyn Solver, input: &Path) -> Result<String, String> {
+    read_input(input).map_err(|e| format!("Day {}: cannot read {}: {}", solver.day(), input.display(), e))
+}
+
+/// The input as a stream, to be parsed while it is read.
+fn open_input(path: &Path) -> io::Result<Box<dyn BufRead>> {
+    if path == Path::new("-") {
+        Ok(Box::new(io::stdin().lock()))
+    } else {
+        Ok(Box::new(io::BufReader::new(fs::File::open(path)?)))
+    }
+}
+
+/// The name of the variant to solve a part with, the default one unless
+/// another was asked for.
+fn variant_name(solver: &dyn Solver, part: u8, variant: Option<&str>) -> &'static str {
+    let variants = solver.variants(part);
+    match variant {
+        Some(variant) => variants
+            .into_iter()
+            .find(|name| *name == variant)
+            .expect("Variant checked before"),
+        None => variants[0],
+    }
+}
+
+fn selected_parts(part: Option<u8>) -> Vec<u8> {
+    match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    }
+}
+
+fn run_day(
+    solver: &dyn Solver,
+    input: &Path,
+    part: Option<u8>,
+    variant: Option<&str>,
+    iterations: usize,
+) -> Result<DayResult, String> {
+    let (puzzle, parse) = if iterations > 1 {
+        // Parsed more than once, so kept in memory instead of read again
+        let input = read_day_input(solver, input)?;
+        measure(iterations, || solver.read(&mut input.as_bytes()))
+    } else {
+        let mut reader = open_input(input)
+            .map_err(|e| format!("Day {}: cannot read {}: {}", solver.day(), input.display(), e))?;
+        measure(1, || solver.read(&mut reader))
+    };
+    let puzzle = puzzle.map_err(|e| format!("Invalid input: {}", e))?;
+
+    let mut parts = vec![];
+    for p in selected_parts(part) {
+        let variant = variant_name(solver, p, variant);
+        let (answer, timing) = measure(iterations, || solver.solve_variant(p, variant, puzzle.as_ref()));
+        parts.push(PartResult { part: p, variant, answer, timing });
+    }
+    Ok(DayResult { day: solver.day(), parse, parts })
+}
+
+/// Solve the selected parts with every variant, each in its own thread so
+/// that one that never finishes can be abandoned after `timeout`.
+fn cross_check(solver: &'static dyn Solver, input: &Path, part: Option<u8>, timeout: Duration) -> bool {
+    let input = match read_day_input(solver, input) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("{}", e);
+            return false;
+        }
+    };
+    if let Err(e) = solver.read(&mut input.as_bytes()) {
+        eprintln!("Invalid input: {}", e);
+        return false;
+    }
+
+    println!("Day {}", solver.day());
+    let mut ok = true;
+    for p in selected_parts(part) {
+        println!("  Part {}:", p);
+        let mut answers = vec![];
+        for variant in solver.variants(p) {
+            let (sender, receiver) = mpsc::channel();
+            let input = input.clone();
+            thread::spawn(move || {
+                // Parsed again here, the boxed puzzle cannot be shared between threads
+                let puzzle = solver.read(&mut input.as_bytes()).expect("Input parsed before");
+                let _ = sender.send(solver.solve_variant(p, variant, puzzle.as_ref()));
+            });
+            match receiver.recv_timeout(timeout) {
+                Ok(answer) => {
+                    println!("    {}: {}", variant, answer.as_deref().unwrap_or("-"));
+                    answers.push(answer);
+                }
+                Err(_) => {
+                    println!("    {}: no answer after {} s", variant, timeout.as_secs());
+                    ok = false;
+                }
+            }
+        }
+        if answers.windows(2).any(|pair| pair[0] != pair[1]) {
+            println!("  Part {}: variants DISAGREE", p);
+            ok = false;
+        }
+    }
+    ok
+}
+
+fn print_summary(results: &[DayResult]) {
+    println!();
+    println!("Median times in ms");
+    println!("{:>3}  {:>10}  {:>10}  {:>10}  {:>10}", "Day", "Parse", "Part 1", "Part 2", "Total");
+    let mut grand_total = 0.0;
+    for result in results {
+        let mut line = format!("{:>3}  {:>10.3}", result.day, millis(result.parse.median));
+        let mut total = millis(result.parse.median);
+        for p in 1..=2 {
+            match result.parts.iter().find(|x| x.part == p) {
+                Some(part) => {
+                    line.push_str(&format!("  {:>10.3}", millis(part.timing.median)));
+                    total += millis(part.timing.median);
+                }
+                None => line.push_str(&format!("  {:>10}", "-")),
+            }
+        }
+        println!("{}  {:>10.3}", line, total);
+        grand_total += total;
+    }
+    println!("{:<3}  {:>46.3}", "All", grand_total);
+}
+
+fn run(args: RunArgs) -> bool {
+    let days: Vec<(&'static dyn Solver, PathBuf)> = if args.all {
+        registry::all()
+            .iter()
+            .map(|&s| (s, default_input(s.day())))
+            .collect()
+    } else {
+        // clap guarantees a valid day when --all is absent
+        let day = args.day.unwrap();
+        let solver = registry::get(day).expect("Day not registered");
+        let input = args.input.unwrap_or_else(|| default_input(day));
+        if let Some(variant) = &args.variant {
+            // clap guarantees a part when --variant is given
+            let part = args.part.unwrap();
+            let variants = solver.variants(part);
+            if !variants.contains(&variant.as_str()) {
+                eprintln!(
+                    "Day {} part {} has no variant {}, choose from: {}",
+                    day,
+                    part,
+                    variant,
+                    variants.join(", ")
+                );
+                return false;
+            }
+        }
+        vec![(solver, input)]
+    };
+
+    if args.cross_check {
+        let timeout = Duration::from_secs(args.timeout);
+        let mut ok = true;
+        for (solver, input) in days {
+            ok &= cross_check(solver, &input, args.part, timeout);
+        }
+        return ok;
+    }
+
+    let mut known = if args.record || args.verify {
+        match Answers::load(&args.answers) {
+            Ok(answers) => answers,
+            // Recording starts a new file if needed
+            Err(e) if args.record && e.kind() == io::ErrorKind::NotFound => Answers::default(),
+            Err(e) => {
+                eprintln!("Cannot read answers from {}: {}", args.answers.display(), e);
+                return false;
+            }
+        }
+    } else {
+        Answers::default()
+    };
+
+    let iterations = if args.time { args.iterations } else { 1 };
+    let (part, variant) = (args.part, args.variant.as_deref());
+    let solve = |(solver, input): &(&'static dyn Solver, PathBuf)| {
+        (*solver, run_day(*solver, input, part, variant, iterations))
+    };
+    // Sequentially every day is printed as soon as it is solved
+    let solved: Box<dyn Iterator<Item = _>> = Box::new(days.iter().map(solve));
+    #[cfg(feature = "parallel")]
+    let solved = if args.parallel {
+        Box::new(days.par_iter().map(solve).collect::<Vec<_>>().into_iter())
+    } else {
+        solved
+    };
+
+    let mut results = vec![];
+    let mut ok = true;
+    for (solver, result) in solved {
+        let result = match result {
+            Ok(result) => result,
+            Err(e) => {
+                match args.format {
+                    Format::Text => eprintln!("{}", e),
+                    Format::Json => {
+                        for p in selected_parts(args.part) {
+                            let variant = variant_name(solver, p, args.variant.as_deref());
+                            println!("{}", Record::error(solver.day(), p, variant, &e).to_json());
+                        }
+                    }
+                }
+                ok = false;
+                continue;
+            }
+        };
+
+        if args.format == Format::Text {
+            println!("Day {}", solver.day());
+            if args.time {
+                println!(
+                    "  Parse: min {:.3} ms, median {:.3} ms",
+                    millis(result.parse.min),
+                    millis(result.parse.median)
+                );
+            }
+        }
+        for PartResult { part, variant, answer, timing } in result.parts.iter() {
+            let (part, answer) = (*part, answer.as_deref());
+            let check = if args.verify {
+                Some(known.check(solver.day(), part, answer))
+            } else {
+                None
+            };
+            if let Some(Check::Mismatch(_)) = check {
+                ok = false;
+            }
+            if args.record {
+                if let Some(answer) = answer {
+                    known.set(solver.day(), part, answer);
+                }
+            }
+
+            match args.format {
+                Format::Text => {
+                    let status = match &check {
+                        Some(Check::Match) => String::from(" (ok)"),
+                        Some(Check::Mismatch(expected)) => format!(" (MISMATCH, expected {})", expected),
+                        Some(Check::Unrecorded) => String::from(" (not recorded)"),
+                        None => String::new(),
+                    };
+                    println!("  Part {}: {}{}", part, answer.unwrap_or("-"), status);
+                    if args.time {
+                        println!(
+                            "          min {:.3} ms, median {:.3} ms",
+                            millis(timing.min),
+                            millis(timing.median)
+                        );
+                    }
+                }
+                Format::Json => {
+                    let (status, expected) = match &check {
+                        Some(Check::Match) => ("ok", None),
+                        Some(Check::Mismatch(expected)) => ("mismatch", Some(expected.as_str())),
+                        Some(Check::Unrecorded) => ("unrecorded", None),
+                        None if args.record => ("recorded", None),
+                        None => ("solved", None),
+                    };
+                    let record = Record {
+                        day: solver.day(),
+                        part,
+                        variant,
+                        answer,
+                        parse_ms: Some(millis(result.parse.median)),
+                        solve_ms: Some(millis(timing.median)),
+                        status,
+                        expected,
+                        error: None,
+                    };
+                    println!("{}", record.to_json());
+                }
+            }
+        }
+        results.push(result);
+    }
+
+    if args.time && args.format == Format::Text {
+        print_summary(&results);
+    }
+
+    if args.record {
+        if let Err(e) = known.save(&args.answers) {
+            eprintln!("Cannot write answers to {}: {}", args.answers.display(), e);
+            ok = false;
+        }
+    }
+    ok
+}
+
+fn new(args: NewArgs) -> bool {
+    let input = match &args.example {
+        Some(path) => match read_input(path) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("Cannot read example from {}: {}", path.display(), e);
+                return false;
+            }
+        },
+        None => String::new(),
+    };
+    let example = Example {
+        input,
+        answer1: args.answer1.unwrap_or_default(),
+        answer2: args.answer2.unwrap_or_default(),
+    };
+
+    match scaffold::new_day(&args.root, args.day, &example) {
+        Ok(()) => {
+            println!("Created day {}, solve it with: aoc run --day {}", args.day, args.day);
+            true
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            false
+        }
+    }
+}
+
+fn gen(args: GenArgs) -> bool {
+    let solver = registry::get(args.day).expect("Day not registered");
+    let generated = match solver.generate(args.seed, args.size) {
+        Some(generated) => generated,
+        None => {
+            eprintln!("Day {} has no input generator", args.day);
+            return false;
+        }
+    };
+
+    match &args.output {
+        Some(path) => {
+            if let Err(e) = fs::write(path, format!("{}\n", generated.input)) {
+                eprintln!("Cannot write {}: {}", path.display(), e);
+                return false;
+            }
+        }
+        None => println!("{}", generated.input),
+    }
+    // On stderr, so the input can be piped into aoc run
+    for (part, answer) in [(1, &generated.answer1), (2, &generated.answer2)].iter() {
+        eprintln!("Part {}: {}", part, answer.as_deref().unwrap_or("unknown"));
+    }
+    true
+}
+
+fn examples(args: ExamplesArgs) -> bool {
+    let page = match fs::read_to_string(&args.page) {
+        Ok(page) => page,
+        Err(e) => {
+            eprintln!("Cannot read {}: {}", args.page.display(), e);
+            return false;
+        }
+    };
+    let found = examples::extract(&page);
+    if found.is_empty() {
+        eprintln!("No examples in {}", args.page.display());
+        return false;
+    }
+
+    match examples::write(&args.root, args.day, &found) {
+        Ok(written) => {
+            for path in written {
+                println!("Wrote {}", path.display());
+            }
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            return false;
+        }
+    }
+    for (part, example) in (1..=2).zip(found.iter()) {
+        if example.answer.is_none() {
+            eprintln!("No answer for the example of part {}, the tests will skip it", part);
+        }
+    }
+    true
+}
+
+fn debug(args: DebugArgs) -> bool {
+    let day = args.day.strip_prefix("day").unwrap_or(&args.day).parse::<u8>();
+    if day != Ok(8) {
+        eprintln!("Cannot debug {}, only day8 has a program", args.day);
+        return false;
+    }
+    let path = args.input.unwrap_or_else(|| default_input(8));
+    let source = match fs::read_to_string(&path) {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("Cannot read {}: {}", path.display(), e);
+            return false;
+        }
+    };
+    // The assembler takes plain puzzle inputs as well
+    let program = match vm::Program::assemble(&source) {
+        Ok(program) => program,
+        Err(e) => {
+            eprintln!("Invalid program: {}", e);
+            return false;
+        }
+    };
+
+    let mut debugger = Debugger::new(program, args.feed);
+    if let Err(e) = debug::repl(&mut debugger, &mut io::stdin().lock(), &mut io::stdout()) {
+        eprintln!("{}", e);
+        return false;
+    }
+    true
+}
+
+fn init_logging(verbose: u8) {
+    let level = match verbose {
+        0 => "warn",
+        1 => "info",
+        2 => "debug",
+        _ => "trace",
+    };
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or(level))
+        .format_timestamp(None)
+        .init();
+}
+
+fn main() {
+    let cli = Cli::parse();
+    init_logging(cli.verbose);
+
+    let ok = match cli.command {
+        Command::Run(args) => run(args),
+        Command::New(args) => new(args),
+        Command::Gen(args) => gen(args),
+        Command::Examples(args) => examples(args),
+        Command::Debug(args) => debug(args),
+    };
+    if !ok {
+        process::exit(1);
+    }
+}
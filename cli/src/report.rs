@@ -0,0 +1,70 @@
+use serde::Serialize;
+
+/// One solved part, as written by `--format json`: a single line per record
+/// so the output can be consumed line by line.
+#[derive(Debug, Serialize)]
+pub struct Record<'a> {
+    pub day: u8,
+    pub part: u8,
+    pub variant: &'a str,
+    pub answer: Option<&'a str>,
+    pub parse_ms: Option<f64>,
+    pub solve_ms: Option<f64>,
+    /// One of `solved`, `recorded`, `ok`, `mismatch`, `unrecorded` or `error`
+    pub status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<&'a str>,
+}
+
+impl<'a> Record<'a> {
+    /// A part that could not be solved because the input was unusable.
+    pub fn error(day: u8, part: u8, variant: &'a str, error: &'a str) -> Record<'a> {
+        Record {
+            day,
+            part,
+            variant,
+            answer: None,
+            parse_ms: None,
+            solve_ms: None,
+            status: "error",
+            expected: None,
+            error: Some(error),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("Records always serialize")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Record;
+
+    #[test]
+    fn test_to_json() {
+        let record = Record {
+            day: 8,
+            part: 2,
+            variant: "dfs",
+            answer: Some("8"),
+            parse_ms: Some(0.5),
+            solve_ms: Some(1.25),
+            status: "mismatch",
+            expected: Some("9"),
+            error: None,
+        };
+        assert_eq!(
+            record.to_json(),
+            r#"{"day":8,"part":2,"variant":"dfs","answer":"8","parse_ms":0.5,"solve_ms":1.25,"status":"mismatch","expected":"9"}"#
+        );
+
+        let record = Record::error(8, 1, "default", "Invalid input: day 8, line 2: unknown operation: \"foo +1\"");
+        assert_eq!(
+            record.to_json(),
+            r#"{"day":8,"part":1,"variant":"default","answer":null,"parse_ms":null,"solve_ms":null,"status":"error","error":"Invalid input: day 8, line 2: unknown operation: \"foo +1\""}"#
+        );
+    }
+}
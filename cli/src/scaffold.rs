@@ -0,0 +1,188 @@
+use std::fs;
+use std::path::Path;
+
+static LIB_TEMPLATE: &str = include_str!("../../template/day.rs");
+static MAIN_TEMPLATE: &str = include_str!("../../template/main.rs");
+static MANIFEST_TEMPLATE: &str = include_str!("../../template/Cargo.toml");
+
+/// What to put in the generated test skeleton.
+#[derive(Default)]
+pub struct Example {
+    pub input: String,
+    pub answer1: String,
+    pub answer2: String,
+}
+
+/// Fill in the placeholders of a template. `{{expectedN}}` is the answer of
+/// a part as an `Option`, `None` while it is not known, so that the tests of
+/// a new day pass until its answers are filled in.
+pub fn render(template: &str, day: u8, example: &Example) -> String {
+    template
+        .replace("{{day}}", &day.to_string())
+        .replace("{{dd}}", &format!("{:02}", day))
+        .replace("{{example}}", &escape(example.input.trim_end()))
+        .replace("{{answer1}}", &escape(&example.answer1))
+        .replace("{{answer2}}", &escape(&example.answer2))
+        .replace("{{expected1}}", &expected(&example.answer1))
+        .replace("{{expected2}}", &expected(&example.answer2))
+}
+
+fn expected(answer: &str) -> String {
+    match answer {
+        "" => String::from("None"),
+        answer => format!("Some(\"{}\")", escape(answer)),
+    }
+}
+
+/// Escape text for use inside a string literal, keeping line breaks as is so
+/// the example reads the same as in the puzzle.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Insert `entry` into a list of per-day lines, keeping the days in order.
+/// `entry_day` recognizes the existing entries; without any, the new one goes
+/// right before the first line matching `fallback`.
+pub fn insert_entry(
+    text: &str,
+    entry: &str,
+    day: u8,
+    entry_day: impl Fn(&str) -> Option<u8>,
+    fallback: impl Fn(&str) -> bool,
+) -> Result<String, String> {
+    let mut lines: Vec<&str> = text.lines().collect();
+    let mut position = None;
+    for (i, line) in lines.iter().enumerate() {
+        match entry_day(line) {
+            Some(d) if d == day => return Err(format!("day {} is already listed", day)),
+            Some(d) if d < day => position = Some(i + 1),
+            Some(_) => {
+                position = position.or(Some(i));
+                break;
+            }
+            None => (),
+        }
+    }
+    let position = match position {
+        Some(position) => position,
+        None => lines
+            .iter()
+            .position(|line| fallback(line))
+            .ok_or_else(|| String::from("cannot find where to add the day"))?,
+    };
+    lines.insert(position, entry);
+
+    let mut result = lines.join("\n");
+    result.push('\n');
+    Ok(result)
+}
+
+fn lib_day(line: &str) -> Option<u8> {
+    line.strip_prefix("pub mod day")?.strip_suffix(';')?.parse().ok()
+}
+
+fn registry_day(line: &str) -> Option<u8> {
+    line.trim().strip_prefix("&day")?.split("::").next()?.parse().ok()
+}
+
+fn member_day(line: &str) -> Option<u8> {
+    line.trim().strip_prefix("\"day")?.strip_suffix("\",")?.parse().ok()
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    fs::write(path, contents).map_err(|e| format!("Cannot write {}: {}", path.display(), e))
+}
+
+/// Create the library module, the binary crate and an empty input for a new
+/// day in the workspace at `root`, and register it everywhere.
+pub fn new_day(root: &Path, day: u8, example: &Example) -> Result<(), String> {
+    let module = root.join("aoc").join("src").join(format!("day{:02}.rs", day));
+    let krate = root.join(format!("day{}", day));
+    for path in [&module, &krate].iter() {
+        if path.exists() {
+            return Err(format!("{} already exists", path.display()));
+        }
+    }
+
+    // Work out all changes first, so nothing is touched if one of them fails
+    let lib = root.join("aoc").join("src").join("lib.rs");
+    let registry = root.join("aoc").join("src").join("registry.rs");
+    let manifest = root.join("Cargo.toml");
+    let in_file = |path: &Path, e: String| format!("{}: {}", path.display(), e);
+    let new_lib = insert_entry(
+        &read(&lib)?,
+        &format!("pub mod day{:02};", day),
+        day,
+        lib_day,
+        |line| line.starts_with("pub mod"),
+    )
+    .map_err(|e| in_file(&lib, e))?;
+    let new_registry = insert_entry(
+        &read(&registry)?,
+        &format!("    &day{:02}::Day{:02},", day, day),
+        day,
+        registry_day,
+        |line| line.trim() == "];",
+    )
+    .map_err(|e| in_file(&registry, e))?;
+    let new_manifest = insert_entry(
+        &read(&manifest)?,
+        &format!("    \"day{}\",", day),
+        day,
+        member_day,
+        |line| line.trim() == "]",
+    )
+    .map_err(|e| in_file(&manifest, e))?;
+
+    write(&module, &render(LIB_TEMPLATE, day, example))?;
+    let src = krate.join("src");
+    fs::create_dir_all(&src).map_err(|e| format!("Cannot create {}: {}", src.display(), e))?;
+    write(&src.join("main.rs"), &render(MAIN_TEMPLATE, day, example))?;
+    write(&krate.join("Cargo.toml"), &render(MANIFEST_TEMPLATE, day, example))?;
+    write(&krate.join("input"), "")?;
+    write(&lib, &new_lib)?;
+    write(&registry, &new_registry)?;
+    write(&manifest, &new_manifest)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_render() {
+        let example = super::Example {
+            input: String::from("1 \"a\"\n2 \\b\n"),
+            answer1: String::from("3"),
+            answer2: String::new(),
+        };
+        let ans = super::render("Day{{dd}} {{day}}: \"{{example}}\" {{answer1}}/{{answer2}}", 7, &example);
+        assert_eq!(ans, "Day07 7: \"1 \\\"a\\\"\n2 \\\\b\" 3/");
+
+        let ans = super::render("{{expected1}} {{expected2}}", 7, &example);
+        assert_eq!(ans, "Some(\"3\") None");
+    }
+
+    #[test]
+    fn test_insert_entry() {
+        let members = "[workspace]\nmembers = [\n    \"aoc\",\n    \"day2\",\n    \"day10\",\n]\n";
+        let ans = super::insert_entry(members, "    \"day9\",", 9, super::member_day, |x| x == "]");
+        assert_eq!(
+            ans,
+            Ok(String::from("[workspace]\nmembers = [\n    \"aoc\",\n    \"day2\",\n    \"day9\",\n    \"day10\",\n]\n"))
+        );
+
+        let ans = super::insert_entry(members, "    \"day1\",", 1, super::member_day, |x| x == "]");
+        assert!(ans.unwrap().contains("\"aoc\",\n    \"day1\",\n    \"day2\""));
+
+        let ans = super::insert_entry(members, "    \"day2\",", 2, super::member_day, |x| x == "]");
+        assert!(ans.is_err());
+
+        // Without any days yet, the first one goes before the fallback line
+        let lib = "pub mod error;\npub mod solution;\n";
+        let ans = super::insert_entry(lib, "pub mod day01;", 1, super::lib_day, |x| x.starts_with("pub mod"));
+        assert_eq!(ans, Ok(String::from("pub mod day01;\npub mod error;\npub mod solution;\n")));
+    }
+}
@@ -0,0 +1,79 @@
+use std::time::{Duration, Instant};
+
+/// Fastest and median duration over a number of runs.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Timing {
+    pub min: Duration,
+    pub median: Duration,
+}
+
+impl Timing {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Timing {
+        if samples.is_empty() {
+            return Timing::default();
+        }
+        samples.sort();
+        Timing {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+        }
+    }
+}
+
+/// Run `f` the given number of times (at least once), returning the result of
+/// the last run and how long the runs took.
+pub fn measure<T, F>(iterations: usize, mut f: F) -> (T, Timing)
+where
+    F: FnMut() -> T,
+{
+    let mut samples = vec![];
+    let mut result = None;
+    for _ in 0..iterations.max(1) {
+        let start = Instant::now();
+        result = Some(f());
+        samples.push(start.elapsed());
+    }
+
+    (result.expect("Ran at least once"), Timing::from_samples(samples))
+}
+
+pub fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::Timing;
+
+    #[test]
+    fn test_from_samples() {
+        let samples = vec![
+            Duration::from_millis(5),
+            Duration::from_millis(1),
+            Duration::from_millis(9),
+            Duration::from_millis(3),
+            Duration::from_millis(4),
+        ];
+        let timing = Timing::from_samples(samples);
+        assert_eq!(timing.min, Duration::from_millis(1));
+        assert_eq!(timing.median, Duration::from_millis(4));
+
+        assert_eq!(Timing::from_samples(vec![]), Timing::default());
+    }
+
+    #[test]
+    fn test_measure() {
+        let mut runs = 0;
+        let (result, _) = super::measure(3, || {
+            runs += 1;
+            runs * 2
+        });
+        assert_eq!(runs, 3);
+        assert_eq!(result, 6);
+
+        let (_, _) = super::measure(0, || runs += 1);
+        assert_eq!(runs, 4);
+    }
+}
@@ -0,0 +1,7 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+// Parsing day 1 must reject bad input with a ParseError, never panic.
+fuzz_target!(|data: &[u8]| {
+    let _ = aoc::registry::get(1).unwrap().parse(&String::from_utf8_lossy(data));
+});
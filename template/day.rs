@@ -0,0 +1,52 @@
+use crate::error::ParseError;
+use crate::solution::Solution;
+
+pub fn star_one(_lines: &[String]) -> Option<isize> {
+    None
+}
+
+pub fn star_two(_lines: &[String]) -> Option<isize> {
+    None
+}
+
+pub struct Day{{dd}};
+
+impl Solution for Day{{dd}} {
+    const DAY: u8 = {{day}};
+    type Puzzle = Vec<String>;
+
+    fn parse_lines(lines: impl Iterator<Item = String>) -> Result<Self::Puzzle, ParseError> {
+        Ok(lines.collect())
+    }
+
+    fn part1(lines: &Self::Puzzle) -> Option<String> {
+        star_one(lines).map(|x| x.to_string())
+    }
+
+    fn part2(lines: &Self::Puzzle) -> Option<String> {
+        star_two(lines).map(|x| x.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solution::Solution;
+
+    static TEST_DATA: &str = "{{example}}";
+
+    #[test]
+    fn test_star_one() {
+        let lines = super::Day{{dd}}::parse(TEST_DATA).expect("Invalid test data");
+
+        let ans = super::Day{{dd}}::part1(&lines);
+        assert_eq!(ans.as_deref(), {{expected1}});
+    }
+
+    #[test]
+    fn test_star_two() {
+        let lines = super::Day{{dd}}::parse(TEST_DATA).expect("Invalid test data");
+
+        let ans = super::Day{{dd}}::part2(&lines);
+        assert_eq!(ans.as_deref(), {{expected2}});
+    }
+}
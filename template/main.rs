@@ -1,34 +1,15 @@
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::vec::Vec;
-
-fn star_one(_lines: Vec<String>) -> isize {
-    -1
-}
+use aoc::day{{dd}}::Day{{dd}};
+use aoc::Solution;
 
 fn main() {
-    let file = File::open("./input").expect("Unreadable input file ./input");
-    let lines: Vec<String> = io::BufReader::new(file)
-        .lines()
-        .map(|x| x.expect("Could not read line"))
-        .collect();
-
-    let ans = star_one(lines);
-    println!("Star one: {}", ans);
-}
-
-#[cfg(test)]
-mod tests {
-    static TEST_DATA: &str = "";
+    let lines = aoc::read_lines("./input")
+        .expect("Unreadable input file ./input")
+        .map(|x| x.expect("Could not read line"));
+    let lines = Day{{dd}}::parse_lines(lines).expect("Invalid input data");
 
-    #[test]
-    fn test_star_one() {
-        let lines: Vec<String> = TEST_DATA
-            .lines()
-            .map(|x| x.to_string())
-            .collect();
+    let ans = Day{{dd}}::part1(&lines);
+    println!("Star one: {}", ans.as_deref().unwrap_or("-"));
 
-        let ans = super::star_one(lines);
-        assert_eq!(ans, 100);
-    }
+    let ans = Day{{dd}}::part2(&lines);
+    println!("Star two: {}", ans.as_deref().unwrap_or("-"));
 }
@@ -0,0 +1,100 @@
+//! The solvers for JavaScript, to run them in a browser (see `www/`) or
+//! under Node. Build with `wasm-pack build --target web wasm`.
+
+use wasm_bindgen::prelude::*;
+
+use aoc::automaton::Sparse;
+use aoc::{day11, day17, day20, registry};
+
+/// All days that can be solved, in calendar order.
+#[wasm_bindgen]
+pub fn days() -> Vec<u8> {
+    registry::all().iter().map(|s| s.day()).collect()
+}
+
+/// The answer to one part of a day, or `-` if the input has none. Throws
+/// for a day or part that does not exist and for invalid input.
+#[wasm_bindgen]
+pub fn solve(day: u8, part: u8, input: &str) -> Result<String, String> {
+    let solver = registry::get(day).ok_or_else(|| format!("Day {} has no solution", day))?;
+    let puzzle = solver.parse(input).map_err(|e| e.to_string())?;
+    let answer = match part {
+        1 => solver.part1(puzzle.as_ref()),
+        2 => solver.part2(puzzle.as_ref()),
+        _ => return Err(format!("Day {} has no part {}", day, part)),
+    };
+    Ok(answer.unwrap_or_else(|| String::from("-")))
+}
+
+/// What the puzzle looks like once a part is solved, for the days that can
+/// show it: the seats of day 11 after everybody sat down, the pocket
+/// dimension of day 17 after booting and the sea monsters of day 20.
+/// `None` for all other days.
+#[wasm_bindgen]
+pub fn render(day: u8, part: u8, input: &str) -> Result<Option<String>, String> {
+    let picture = match day {
+        11 => {
+            let mut ferry = day11::Ferry::from_layout(input.lines()).map_err(|e| e.to_string())?;
+            match part {
+                1 => day11::star_one(&mut ferry),
+                _ => day11::star_two(&mut ferry),
+            };
+            ferry.to_string()
+        }
+        17 => {
+            let slice = day17::parse_input(input.lines()).map_err(|e| e.to_string())?;
+            match part {
+                1 => booted(day17::pocket_dimension::<3>(&slice)),
+                _ => booted(day17::pocket_dimension::<4>(&slice)),
+            }
+        }
+        20 => {
+            let tiles = day20::parse_input(&mut input.lines().peekable()).map_err(|e| e.to_string())?;
+            day20::find_sea_monsters(&tiles).to_string()
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(picture))
+}
+
+fn booted<const N: usize>(mut dimension: Sparse<N>) -> String {
+    dimension.run(6);
+    dimension.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    static DAY8: &str = "nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6\n";
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(super::days(), (1..=25).collect::<Vec<u8>>());
+        assert_eq!(super::solve(8, 1, DAY8), Ok(String::from("5")));
+        assert_eq!(super::solve(8, 2, DAY8), Ok(String::from("8")));
+
+        assert!(super::solve(26, 1, DAY8).is_err());
+        assert!(super::solve(8, 3, DAY8).is_err());
+        let err = super::solve(8, 1, "nop +0\nfoo +1\n").expect_err("Invalid input accepted");
+        assert!(err.starts_with("day 8, line 2"), "{}", err);
+    }
+
+    #[test]
+    fn test_render() {
+        // Every occupied seat and active cube is shown
+        let seats = "L.LL.LL.LL\nLLLLLLL.LL\nL.L.L..L..\nLLLL.LL.LL\nL.LL.LL.LL\n";
+        for part in 1..=2 {
+            let layout = super::render(11, part, seats).expect("Invalid test data").expect("Nothing shown");
+            assert_eq!(layout.lines().count(), 5);
+            assert_eq!(Ok(layout.matches('#').count().to_string()), super::solve(11, part, seats));
+        }
+
+        let slice = ".#.\n..#\n###";
+        for part in 1..=2 {
+            let dimension = super::render(17, part, slice).expect("Invalid test data").expect("Nothing shown");
+            assert_eq!(Ok(dimension.matches('#').count().to_string()), super::solve(17, part, slice));
+        }
+
+        assert_eq!(super::render(8, 1, DAY8), Ok(None));
+        assert!(super::render(11, 1, "L.X\n").is_err());
+    }
+}
@@ -0,0 +1,21 @@
+//! The JavaScript API as Node sees it, run with `wasm-pack test --node wasm`.
+
+#![cfg(target_arch = "wasm32")]
+
+use wasm_bindgen_test::*;
+
+static DAY1: &str = "1721\n979\n366\n299\n675\n1456\n";
+
+#[wasm_bindgen_test]
+fn test_solve() {
+    assert_eq!(aoc_wasm::solve(1, 1, DAY1), Ok(String::from("514579")));
+    assert_eq!(aoc_wasm::solve(1, 2, DAY1), Ok(String::from("241861950")));
+    assert!(aoc_wasm::solve(1, 1, "1721\nx\n").is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_render() {
+    let layout = aoc_wasm::render(11, 1, "L.L\nLLL\n").expect("Invalid test data");
+    assert!(layout.is_some());
+    assert_eq!(aoc_wasm::render(1, 1, DAY1), Ok(None));
+}
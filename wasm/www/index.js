@@ -0,0 +1,34 @@
+// Build the package first with `wasm-pack build --target web wasm`, then
+// serve the wasm directory, e.g. `python3 -m http.server -d wasm`, and open
+// http://localhost:8000/www/
+import init, { days, solve, render } from "../pkg/aoc_wasm.js";
+
+const day = document.getElementById("day");
+const input = document.getElementById("input");
+
+function show(part) {
+  const answer = document.getElementById(`part${part}`);
+  const picture = document.getElementById(`render${part}`);
+  answer.className = "";
+  picture.hidden = true;
+  try {
+    answer.textContent = solve(Number(day.value), part, input.value);
+    const drawn = render(Number(day.value), part, input.value);
+    if (drawn !== undefined) {
+      picture.textContent = drawn;
+      picture.hidden = false;
+    }
+  } catch (e) {
+    answer.textContent = e;
+    answer.className = "error";
+  }
+}
+
+await init();
+for (const d of days()) {
+  day.add(new Option(`Day ${d}`, d));
+}
+document.getElementById("solve").addEventListener("click", () => {
+  show(1);
+  show(2);
+});